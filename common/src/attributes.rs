@@ -1,42 +1,115 @@
 use std::collections::HashMap;
-use syn::{Lit, Meta, MetaNameValue, NestedMeta};
+use std::fmt;
+use std::str::FromStr;
+use syn::{Lit, LitStr, Meta, MetaNameValue, NestedMeta};
 
 use crate::constants::{
-    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
+    DEFAULT_MAX_BYTECODE_SIZE, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE,
+    DEFAULT_MAX_TRACE_LENGTH, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
 };
 
+/// The polynomial commitment scheme a provable function is proven with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommitmentSchemeKind {
+    #[default]
+    Hyrax,
+    HyperKZG,
+    Zeromorph,
+}
+
+impl FromStr for CommitmentSchemeKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "hyrax" => Ok(Self::Hyrax),
+            "hyperkzg" => Ok(Self::HyperKZG),
+            "zeromorph" => Ok(Self::Zeromorph),
+            _ => Err(format!(
+                "invalid pcs \"{}\", expected one of \"hyrax\", \"hyperkzg\", \"zeromorph\"",
+                value
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CommitmentSchemeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hyrax => write!(f, "hyrax"),
+            Self::HyperKZG => write!(f, "hyperkzg"),
+            Self::Zeromorph => write!(f, "zeromorph"),
+        }
+    }
+}
+
+impl CommitmentSchemeKind {
+    fn from_attribute(lit: &LitStr) -> syn::Result<Self> {
+        lit.value()
+            .parse()
+            .map_err(|err: String| syn::Error::new_spanned(lit, err))
+    }
+}
+
 pub struct Attributes {
     pub wasm: bool,
+    pub pcs: CommitmentSchemeKind,
     pub memory_size: u64,
     pub stack_size: u64,
     pub max_input_size: u64,
     pub max_output_size: u64,
+    pub max_bytecode_size: u64,
+    pub max_trace_length: u64,
+    /// The number of cycles the guest may run for before tracing is aborted. Instructions
+    /// expanding into virtual sequences add several rows to the trace in one cycle, so this
+    /// is not `max_trace_length`, but defaults to it as no execution fitting the trace can
+    /// run for longer.
+    pub max_cycles: u64,
 }
 
 pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
+    try_parse_attributes(attr).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Like `parse_attributes`, but returns an error pointing at the offending attribute
+/// instead of panicking.
+pub fn try_parse_attributes(attr: &Vec<NestedMeta>) -> syn::Result<Attributes> {
     let mut attributes = HashMap::<_, u64>::new();
     let mut wasm = false;
+    let mut pcs = CommitmentSchemeKind::default();
 
     for attr in attr {
         match attr {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit),
+                ..
+            })) if path.is_ident("pcs") => {
+                pcs = CommitmentSchemeKind::from_attribute(lit)?;
+            }
             NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
                 let value: u64 = match lit {
-                    Lit::Int(lit) => lit.base10_parse().unwrap(),
-                    _ => panic!("expected integer literal"),
+                    Lit::Int(lit) => lit.base10_parse()?,
+                    _ => return Err(syn::Error::new_spanned(lit, "expected integer literal")),
                 };
-                let ident = &path.get_ident().expect("Expected identifier");
+                let ident = path
+                    .get_ident()
+                    .ok_or_else(|| syn::Error::new_spanned(path, "expected identifier"))?;
                 match ident.to_string().as_str() {
                     "memory_size" => attributes.insert("memory_size", value),
                     "stack_size" => attributes.insert("stack_size", value),
                     "max_input_size" => attributes.insert("max_input_size", value),
                     "max_output_size" => attributes.insert("max_output_size", value),
-                    _ => panic!("invalid attribute"),
+                    "max_bytecode_size" => attributes.insert("max_bytecode_size", value),
+                    "max_trace_length" => attributes.insert("max_trace_length", value),
+                    "max_cycles" => attributes.insert("max_cycles", value),
+                    _ => return Err(syn::Error::new_spanned(ident, "invalid attribute")),
                 };
             }
             NestedMeta::Meta(Meta::Path(path)) if path.is_ident("wasm") => {
                 wasm = true;
             }
-            _ => panic!("expected integer literal"),
+            _ => return Err(syn::Error::new_spanned(attr, "invalid attribute")),
         }
     }
 
@@ -50,12 +123,23 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
     let max_output_size = *attributes
         .get("max_output_size")
         .unwrap_or(&DEFAULT_MAX_OUTPUT_SIZE);
+    let max_bytecode_size = *attributes
+        .get("max_bytecode_size")
+        .unwrap_or(&DEFAULT_MAX_BYTECODE_SIZE);
+    let max_trace_length = *attributes
+        .get("max_trace_length")
+        .unwrap_or(&DEFAULT_MAX_TRACE_LENGTH);
+    let max_cycles = *attributes.get("max_cycles").unwrap_or(&max_trace_length);
 
-    Attributes {
+    Ok(Attributes {
         wasm,
+        pcs,
         memory_size,
         stack_size,
         max_input_size,
         max_output_size,
-    }
+        max_bytecode_size,
+        max_trace_length,
+        max_cycles,
+    })
 }
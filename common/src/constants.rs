@@ -1,8 +1,11 @@
 pub const XLEN: usize = 32;
 const RISCV_REGISTER_COUNT: u64 = 32;
-const VIRTUAL_REGISTER_COUNT: u64 = 32; //  see Section 6.1 of Jolt paper
+pub const VIRTUAL_REGISTER_COUNT: u64 = 32; //  see Section 6.1 of Jolt paper
 pub const REGISTER_COUNT: u64 = RISCV_REGISTER_COUNT + VIRTUAL_REGISTER_COUNT;
 pub const BYTES_PER_INSTRUCTION: usize = 4;
+/// Compressed (RVC) instructions are 2 bytes wide, so instruction addresses are only
+/// guaranteed to be 2-byte aligned.
+pub const ALIGNMENT_FACTOR_BYTECODE: usize = 2;
 pub const REG_OPS_PER_INSTRUCTION: usize = 3;
 pub const RAM_OPS_PER_INSTRUCTION: usize = 4;
 pub const MEMORY_OPS_PER_INSTRUCTION: usize = REG_OPS_PER_INSTRUCTION + RAM_OPS_PER_INSTRUCTION;
@@ -12,6 +15,12 @@ pub const DEFAULT_MEMORY_SIZE: u64 = 10 * 1024 * 1024;
 pub const DEFAULT_STACK_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_INPUT_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_OUTPUT_SIZE: u64 = 4096;
+/// Size of the buffer a panicking guest writes its panic message and location to.
+pub const MAX_PANIC_INFO_SIZE: u64 = 512;
+/// Guests print by storing bytes to this address, which is not part of the program IO.
+pub const LOG_ADDRESS: u64 = RAM_START_ADDRESS - 1;
+pub const DEFAULT_MAX_BYTECODE_SIZE: u64 = 1 << 20;
+pub const DEFAULT_MAX_TRACE_LENGTH: u64 = 1 << 24;
 
 pub const fn memory_address_to_witness_index(address: u64, ram_witness_offset: u64) -> usize {
     (address + ram_witness_offset - RAM_START_ADDRESS) as usize
@@ -20,12 +29,15 @@ pub const fn witness_index_to_memory_address(index: usize, ram_witness_offset: u
     index as u64 + RAM_START_ADDRESS - ram_witness_offset
 }
 pub const fn virtual_register_index(index: u64) -> u64 {
-    index + VIRTUAL_REGISTER_COUNT
+    index + RISCV_REGISTER_COUNT
 }
+/// The virtual register holding the address reserved by the last LR.W, or zero if no
+/// reservation is set. Only the LR.W and SC.W virtual sequences may write it.
+pub const RESERVATION_REGISTER: u64 = virtual_register_index(VIRTUAL_REGISTER_COUNT - 1);
 
 // Layout of the witness (where || denotes concatenation):
-//     registers || inputs || outputs || panic || padding || RAM
+//     registers || inputs || outputs || panic || termination || panic info || padding || log || RAM
 // Layout of VM memory:
-//     peripheral devices || inputs || outputs || panic || padding || RAM
+//     peripheral devices || inputs || outputs || panic || termination || panic info || padding || log || RAM
 // Notably, we want to be able to map the VM memory address space to witness indices
 // using a constant shift, namely (RAM_WITNESS_OFFSET + RAM_START_ADDRESS)
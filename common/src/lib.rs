@@ -5,5 +5,6 @@ pub fn to_ram_address(index: usize) -> usize {
 pub mod attributes;
 pub mod constants;
 pub mod parallel;
+pub mod precompiles;
 pub mod rv_trace;
 pub mod serializable;
@@ -1,6 +1,8 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
-use crate::constants::{MEMORY_OPS_PER_INSTRUCTION, RAM_START_ADDRESS, REGISTER_COUNT};
+use crate::constants::{
+    LOG_ADDRESS, MAX_PANIC_INFO_SIZE, MEMORY_OPS_PER_INSTRUCTION, RAM_START_ADDRESS, REGISTER_COUNT,
+};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
 use strum_macros::FromRepr;
@@ -11,6 +13,9 @@ pub struct RVTraceRow {
     pub register_state: RegisterState,
     pub memory_state: Option<MemoryState>,
     pub advice_value: Option<u64>,
+    /// For precompiles, the words read from memory, in the order in which the precompile's
+    /// virtual sequence loads them.
+    pub precompile_input: Option<Vec<u64>>,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
@@ -115,7 +120,8 @@ impl From<&RVTraceRow> for [MemoryOp; MEMORY_OPS_PER_INSTRUCTION] {
                 | RV32IM::SLTIU
                 | RV32IM::JALR
                 | RV32IM::VIRTUAL_MOVE
-                | RV32IM::VIRTUAL_MOVSIGN => [
+                | RV32IM::VIRTUAL_MOVSIGN
+                | RV32IM::VIRTUAL_ROTRI => [
                     rs1_read(),
                     MemoryOp::noop_read(),
                     rd_write(),
@@ -221,6 +227,8 @@ pub struct ELFInstruction {
     pub rs1: Option<u64>,
     pub rs2: Option<u64>,
     pub rd: Option<u64>,
+    /// For `CUSTOM_0` and `CUSTOM_1` instructions, which have no immediate, this holds
+    /// their funct7 and funct3 fields as `funct7 << 3 | funct3`, see [`Self::custom_funct`].
     pub imm: Option<u32>,
     /// If this instruction is part of a "virtual sequence" (see Section 6.2 of the
     /// Jolt paper), then this contains the number of virtual instructions after this
@@ -228,9 +236,12 @@ pub struct ELFInstruction {
     /// `virtual_sequence_remaining` will be Some(0); if this is the penultimate instruction
     /// in the sequence, `virtual_sequence_remaining` will be Some(1); etc.
     pub virtual_sequence_remaining: Option<usize>,
+    /// Whether this instruction was expanded from a 16-bit compressed (RVC) instruction,
+    /// in which case the next instruction is 2 bytes after it rather than 4.
+    pub is_compressed: bool,
 }
 
-pub const NUM_CIRCUIT_FLAGS: usize = 12;
+pub const NUM_CIRCUIT_FLAGS: usize = 13;
 
 impl ELFInstruction {
     #[rustfmt::skip]
@@ -248,6 +259,7 @@ impl ELFInstruction {
         // 9: Virtual instruction
         // 10: Assert instruction
         // 11: Don't update PC
+        // 12: Compressed instruction
 
         let mut flags = [false; NUM_CIRCUIT_FLAGS];
 
@@ -269,7 +281,8 @@ impl ELFInstruction {
             | RV32IM::SLTIU
             | RV32IM::AUIPC
             | RV32IM::JAL
-            | RV32IM::JALR,
+            | RV32IM::JALR
+            | RV32IM::VIRTUAL_ROTRI,
         );
 
         flags[2] = matches!(
@@ -331,10 +344,13 @@ impl ELFInstruction {
             | RV32IM::SLLI
             | RV32IM::SRLI
             | RV32IM::SRAI
+            | RV32IM::VIRTUAL_ROTRI
             | RV32IM::SLT
             | RV32IM::SLTU
             | RV32IM::SLTI
             | RV32IM::SLTIU
+            | RV32IM::CUSTOM_0
+            | RV32IM::CUSTOM_1
             | RV32IM::BEQ
             | RV32IM::BNE
             | RV32IM::BLT
@@ -366,8 +382,17 @@ impl ELFInstruction {
             None => false
         };
 
+        flags[12] = self.is_compressed;
+
         flags
     }
+
+    /// The funct3 and funct7 fields of a `CUSTOM_0` or `CUSTOM_1` instruction, which select
+    /// among the custom instructions bound to its opcode, or `None` if they were not decoded
+    /// into `imm`.
+    pub fn custom_funct(&self) -> Option<(u32, u32)> {
+        self.imm.map(|funct| (funct & 0x7, funct >> 3))
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -451,8 +476,25 @@ pub enum RV32IM {
     DIVU,
     REM,
     REMU,
+    LR_W,
+    SC_W,
+    AMOSWAP_W,
+    AMOADD_W,
+    AMOXOR_W,
+    AMOAND_W,
+    AMOOR_W,
+    AMOMIN_W,
+    AMOMAX_W,
+    AMOMINU_W,
+    AMOMAXU_W,
     FENCE,
     UNIMPL,
+    // Custom instructions, bound to the custom-0 and custom-1 opcodes by the host
+    CUSTOM_0,
+    CUSTOM_1,
+    // Precompiles, on the custom-2 opcode
+    SHA256_COMPRESS,
+    KECCAK_F,
     // Virtual instructions
     VIRTUAL_MOVSIGN,
     VIRTUAL_MOVE,
@@ -462,6 +504,7 @@ pub enum RV32IM {
     VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER,
     VIRTUAL_ASSERT_EQ,
     VIRTUAL_ASSERT_VALID_DIV0,
+    VIRTUAL_ROTRI,
 }
 
 impl FromStr for RV32IM {
@@ -517,8 +560,23 @@ impl FromStr for RV32IM {
             "DIVU" => Ok(Self::DIVU),
             "REM" => Ok(Self::REM),
             "REMU" => Ok(Self::REMU),
+            "LR.W" => Ok(Self::LR_W),
+            "SC.W" => Ok(Self::SC_W),
+            "AMOSWAP.W" => Ok(Self::AMOSWAP_W),
+            "AMOADD.W" => Ok(Self::AMOADD_W),
+            "AMOXOR.W" => Ok(Self::AMOXOR_W),
+            "AMOAND.W" => Ok(Self::AMOAND_W),
+            "AMOOR.W" => Ok(Self::AMOOR_W),
+            "AMOMIN.W" => Ok(Self::AMOMIN_W),
+            "AMOMAX.W" => Ok(Self::AMOMAX_W),
+            "AMOMINU.W" => Ok(Self::AMOMINU_W),
+            "AMOMAXU.W" => Ok(Self::AMOMAXU_W),
             "FENCE" => Ok(Self::FENCE),
             "UNIMPL" => Ok(Self::UNIMPL),
+            "CUSTOM_0" => Ok(Self::CUSTOM_0),
+            "CUSTOM_1" => Ok(Self::CUSTOM_1),
+            "SHA256_COMPRESS" => Ok(Self::SHA256_COMPRESS),
+            "KECCAK_F" => Ok(Self::KECCAK_F),
             _ => Err("Could not match instruction to RV32IM set.".to_string()),
         }
     }
@@ -556,7 +614,22 @@ impl RV32IM {
             RV32IM::DIV    |
             RV32IM::DIVU   |
             RV32IM::REM    |
-            RV32IM::REMU => RV32InstructionFormat::R,
+            RV32IM::REMU      |
+            RV32IM::LR_W      |
+            RV32IM::SC_W      |
+            RV32IM::AMOSWAP_W |
+            RV32IM::AMOADD_W  |
+            RV32IM::AMOXOR_W  |
+            RV32IM::AMOAND_W  |
+            RV32IM::AMOOR_W   |
+            RV32IM::AMOMIN_W  |
+            RV32IM::AMOMAX_W  |
+            RV32IM::AMOMINU_W |
+            RV32IM::AMOMAXU_W |
+            RV32IM::CUSTOM_0  |
+            RV32IM::CUSTOM_1  |
+            RV32IM::SHA256_COMPRESS |
+            RV32IM::KECCAK_F => RV32InstructionFormat::R,
 
             RV32IM::ADDI         |
             RV32IM::XORI         |
@@ -569,7 +642,8 @@ impl RV32IM {
             RV32IM::FENCE        |
             RV32IM::SLTIU        |
             RV32IM::VIRTUAL_MOVE |
-            RV32IM::VIRTUAL_MOVSIGN=> RV32InstructionFormat::I,
+            RV32IM::VIRTUAL_MOVSIGN |
+            RV32IM::VIRTUAL_ROTRI => RV32InstructionFormat::I,
 
             RV32IM::LB  |
             RV32IM::LH  |
@@ -605,6 +679,11 @@ impl RV32IM {
             RV32IM::UNIMPL => unimplemented!(),
         }
     }
+
+    /// Whether this is a precompile, which the host expands into a virtual sequence.
+    pub fn is_precompile(&self) -> bool {
+        matches!(self, RV32IM::SHA256_COMPRESS | RV32IM::KECCAK_F)
+    }
 }
 
 /// Represented as a "peripheral device" in the RISC-V emulator, this captures
@@ -618,6 +697,11 @@ pub struct JoltDevice {
     pub inputs: Vec<u8>,
     pub outputs: Vec<u8>,
     pub panic: bool,
+    /// Set once the guest writes to the termination address, signalling that it halted.
+    pub termination: bool,
+    /// Bytes the guest's panic handler wrote to the panic info region, see
+    /// [`JoltDevice::guest_panic`].
+    pub panic_info: Vec<u8>,
     pub memory_layout: MemoryLayout,
 }
 
@@ -627,6 +711,8 @@ impl JoltDevice {
             inputs: Vec::new(),
             outputs: Vec::new(),
             panic: false,
+            termination: false,
+            panic_info: Vec::new(),
             memory_layout: MemoryLayout::new(max_input_size, max_output_size),
         }
     }
@@ -642,10 +728,21 @@ impl JoltDevice {
 
     pub fn store(&mut self, address: u64, value: u8) {
         if address == self.memory_layout.panic {
-            println!("GUEST PANIC");
             self.panic = true;
             return;
         }
+        if address == self.memory_layout.termination {
+            self.termination = true;
+            return;
+        }
+        if self.is_panic_info(address) {
+            let index = (address - self.memory_layout.panic_info_start) as usize;
+            if self.panic_info.len() <= index {
+                self.panic_info.resize(index + 1, 0);
+            }
+            self.panic_info[index] = value;
+            return;
+        }
 
         let internal_address = self.convert_write_address(address);
         if self.outputs.len() <= internal_address {
@@ -671,6 +768,24 @@ impl JoltDevice {
         address == self.memory_layout.panic
     }
 
+    pub fn is_termination(&self, address: u64) -> bool {
+        address == self.memory_layout.termination
+    }
+
+    pub fn is_panic_info(&self, address: u64) -> bool {
+        address >= self.memory_layout.panic_info_start
+            && address < self.memory_layout.panic_info_end
+    }
+
+    pub fn is_log(&self, address: u64) -> bool {
+        address == self.memory_layout.log
+    }
+
+    /// The panic message and location of a guest that panicked, or `None` if it did not.
+    pub fn guest_panic(&self) -> Option<GuestPanic> {
+        self.panic.then(|| GuestPanic::decode(&self.panic_info))
+    }
+
     fn convert_read_address(&self, address: u64) -> usize {
         (address - self.memory_layout.input_start) as usize
     }
@@ -692,6 +807,12 @@ pub struct MemoryLayout {
     pub output_start: u64,
     pub output_end: u64,
     pub panic: u64,
+    pub termination: u64,
+    pub panic_info_start: u64,
+    pub panic_info_end: u64,
+    /// Bytes stored here are captured by the host as the guest's log. Unlike the other
+    /// addresses, it is excluded from the program IO the proof attests to.
+    pub log: u64,
 }
 
 impl MemoryLayout {
@@ -705,12 +826,18 @@ impl MemoryLayout {
             output_start: output_start(max_input_size, max_output_size),
             output_end: output_end(max_input_size, max_output_size),
             panic: panic_address(max_input_size, max_output_size),
+            termination: termination_address(max_input_size, max_output_size),
+            panic_info_start: panic_info_start(max_input_size, max_output_size),
+            panic_info_end: panic_info_end(max_input_size, max_output_size),
+            log: LOG_ADDRESS,
         }
     }
 }
 
 pub fn ram_witness_offset(max_input: u64, max_output: u64) -> u64 {
-    (REGISTER_COUNT + max_input + max_output + 1).next_power_of_two()
+    // The inputs and outputs are each followed by an unused byte, then come the panic and
+    // termination bytes. The log byte sits right below RAM.
+    (REGISTER_COUNT + max_input + max_output + 5 + MAX_PANIC_INFO_SIZE).next_power_of_two()
 }
 
 fn input_start(max_input: u64, max_output: u64) -> u64 {
@@ -732,3 +859,65 @@ fn output_end(max_input: u64, max_output: u64) -> u64 {
 fn panic_address(max_input: u64, max_output: u64) -> u64 {
     output_end(max_input, max_output) + 1
 }
+
+fn termination_address(max_input: u64, max_output: u64) -> u64 {
+    panic_address(max_input, max_output) + 1
+}
+
+fn panic_info_start(max_input: u64, max_output: u64) -> u64 {
+    termination_address(max_input, max_output) + 1
+}
+
+fn panic_info_end(max_input: u64, max_output: u64) -> u64 {
+    panic_info_start(max_input, max_output) + MAX_PANIC_INFO_SIZE
+}
+
+/// Size of the header of the panic info region: the line, column, file length and message
+/// length, as little-endian `u32`s. The file and the message follow it.
+pub const PANIC_INFO_HEADER_SIZE: usize = 16;
+
+/// Why and where a guest panicked, as written by the panic handler that `#[jolt::provable]`
+/// generates. The file and message are truncated to fit the panic info region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuestPanic {
+    pub message: String,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl GuestPanic {
+    /// Decodes the panic info region. Guests that panic without writing it, e.g. because
+    /// they were built without `#[jolt::provable]`, yield an empty message and location.
+    pub fn decode(bytes: &[u8]) -> Self {
+        let field = |index: usize| -> u32 {
+            bytes
+                .get(4 * index..4 * index + 4)
+                .map_or(0, |field| u32::from_le_bytes(field.try_into().unwrap()))
+        };
+        let body = bytes.get(PANIC_INFO_HEADER_SIZE..).unwrap_or_default();
+        let file_len = (field(2) as usize).min(body.len());
+        let message_len = (field(3) as usize).min(body.len() - file_len);
+        Self {
+            message: String::from_utf8_lossy(&body[file_len..file_len + message_len]).into_owned(),
+            file: String::from_utf8_lossy(&body[..file_len]).into_owned(),
+            line: field(0),
+            column: field(1),
+        }
+    }
+}
+
+impl fmt::Display for GuestPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "guest panicked")?;
+        if !self.file.is_empty() {
+            write!(f, " at {}:{}:{}", self.file, self.line, self.column)?;
+        }
+        if !self.message.is_empty() {
+            write!(f, ": {}", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GuestPanic {}
@@ -1,7 +1,7 @@
 pub fn main() {
     let (prove_alloc, verify_alloc) = guest::build_alloc();
 
-    let (output, proof) = prove_alloc(41);
+    let (output, proof) = prove_alloc(41).unwrap();
     let is_valid = verify_alloc(proof);
 
     println!("output: {:?}", output);
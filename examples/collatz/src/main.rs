@@ -2,7 +2,7 @@ pub fn main() {
     // Prove/verify convergence for a single number:
     let (prove_collatz_single, verify_collatz_single) = guest::build_collatz_convergence();
 
-    let (output, proof) = prove_collatz_single(19);
+    let (output, proof) = prove_collatz_single(19).unwrap();
     let is_valid = verify_collatz_single(proof);
 
     println!("output: {}", output);
@@ -14,7 +14,7 @@ pub fn main() {
 
     // https://www.reddit.com/r/compsci/comments/gk9x6g/collatz_conjecture_news_recently_i_managed_to/
     let start: u128 = 1 << 68;
-    let (output, proof) = prove_collatz_convergence(start, start + 100);
+    let (output, proof) = prove_collatz_convergence(start, start + 100).unwrap();
     let is_valid = verify_collatz_convergence(proof);
 
     println!("output: {}", output);
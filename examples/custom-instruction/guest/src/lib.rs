@@ -0,0 +1,25 @@
+#![cfg_attr(feature = "guest", no_std)]
+#![no_main]
+
+/// Computes `x & !y` with the ANDN instruction, which the host binds to the custom-0
+/// opcode with funct3 = 1 and funct7 = 0.
+#[jolt::provable]
+fn andn(x: u32, y: u32) -> u32 {
+    #[cfg(target_arch = "riscv32")]
+    {
+        let z: u32;
+        unsafe {
+            core::arch::asm!(
+                ".insn r 0x0b, 0x1, 0x0, {rd}, {rs1}, {rs2}",
+                rd = out(reg) z,
+                rs1 = in(reg) x,
+                rs2 = in(reg) y,
+            );
+        }
+        z
+    }
+    #[cfg(not(target_arch = "riscv32"))]
+    {
+        x & !y
+    }
+}
@@ -0,0 +1,139 @@
+use std::marker::PhantomData;
+
+use jolt_core::jolt::instruction::{JoltCustomInstruction, JoltInstruction, SubtableIndices};
+use jolt_core::jolt::subtable::LassoSubtable;
+use jolt_core::utils::instruction_utils::{chunk_and_concatenate_operands, concatenate_lookups};
+use jolt_core::utils::split_bits;
+use jolt_core::{custom_instruction_set, custom_subtable_enum};
+use jolt_sdk::{host, CommitmentScheme, HyraxScheme, Jolt, JoltField, G, RV32IM};
+use rand::{prelude::StdRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// `x & !y`, bound to the custom-0 opcode with funct3 = 1 and funct7 = 0.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct ANDNInstruction(pub u64, pub u64);
+
+impl JoltInstruction for ANDNInstruction {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, self.1)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], c: usize, m: usize) -> F {
+        concatenate_lookups(vals, c, m.ilog2() as usize / 2)
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        c: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        vec![(Box::new(AndnSubtable::new()), SubtableIndices::from(0..c))]
+    }
+
+    fn to_indices(&self, c: usize, log_m: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, self.1, c, log_m)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        self.0 & !self.1
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        Self(rng.next_u32() as u64, rng.next_u32() as u64)
+    }
+}
+
+impl JoltCustomInstruction for ANDNInstruction {
+    const OPCODE: RV32IM = RV32IM::CUSTOM_0;
+    const FUNCT3: u32 = 1;
+    const FUNCT7: u32 = 0;
+
+    fn from_operands(x: u64, y: u64) -> Self {
+        Self(x, y)
+    }
+}
+
+#[derive(Default)]
+pub struct AndnSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> AndnSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for AndnSubtable<F> {
+    fn materialize(&self, m: usize) -> Vec<F> {
+        // table[x | y] = x & !y
+        let bits_per_operand = m.ilog2() as usize / 2;
+        (0..m)
+            .map(|idx| {
+                let (x, y) = split_bits(idx, bits_per_operand);
+                F::from_u64((x & !y) as u64).unwrap()
+            })
+            .collect()
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // \sum_i 2^i * x_{b - i - 1} * (1 - y_{b - i - 1})
+        let b = point.len() / 2;
+        let (x, y) = point.split_at(b);
+
+        let mut result = F::zero();
+        for i in 0..b {
+            let x = x[b - i - 1];
+            let y = y[b - i - 1];
+            result += F::from_u64(1u64 << i).unwrap() * x * (F::one() - y);
+        }
+        result
+    }
+}
+
+custom_instruction_set!(CustomInstructionSet, ANDN: ANDNInstruction);
+custom_subtable_enum!(CustomSubtables, ANDN: AndnSubtable<F>);
+
+/// RV32I extended with ANDN.
+pub enum CustomJoltVM {}
+
+const C: usize = 4;
+const M: usize = 1 << 16;
+
+impl<F, PCS> Jolt<F, PCS, C, M> for CustomJoltVM
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    type InstructionSet = CustomInstructionSet;
+    type Subtables = CustomSubtables<F>;
+}
+
+pub fn main() {
+    let mut program = host::Program::new("custom-instruction-guest");
+    program.set_input(&0b1100u32);
+    program.set_input(&0b1010u32);
+    let (bytecode, memory_init) = program.decode().unwrap();
+    let (io_device, trace, circuit_flags, _) = program
+        .trace::<jolt_sdk::F, CustomInstructionSet>()
+        .unwrap();
+    let output: u32 = jolt_sdk::postcard::from_bytes(&io_device.outputs).unwrap();
+
+    let preprocessing = CustomJoltVM::preprocess(bytecode, memory_init, 1 << 20, 1 << 20, 1 << 20);
+    let (proof, commitments) = <CustomJoltVM as Jolt<jolt_sdk::F, HyraxScheme<G>, C, M>>::prove(
+        io_device,
+        trace,
+        circuit_flags,
+        preprocessing.clone(),
+    );
+    let is_valid = CustomJoltVM::verify(preprocessing, proof, commitments).is_ok();
+
+    println!("output: {:#b}", output);
+    println!("valid: {}", is_valid);
+}
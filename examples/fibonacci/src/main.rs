@@ -5,7 +5,7 @@ pub fn main() {
         .write_to_file("fib_10.txt".into())
         .expect("should write");
 
-    let (output, proof) = prove_fib(50);
+    let (output, proof) = prove_fib(50).unwrap();
     let is_valid = verify_fib(proof);
 
     println!("output: {}", output);
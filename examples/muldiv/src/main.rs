@@ -1,7 +1,7 @@
 pub fn main() {
     let (prove, verify) = guest::build_muldiv();
 
-    let (output, proof) = prove(12031293, 17, 92);
+    let (output, proof) = prove(12031293, 17, 92).unwrap();
     let is_valid = verify(proof);
 
     println!("output: {}", output);
@@ -0,0 +1,9 @@
+#![cfg_attr(feature = "guest", no_std)]
+#![no_main]
+
+#[jolt::provable]
+fn add(a: u32, b: u32) -> u32 {
+    let sum = a + b;
+    jolt::println!("{} + {} = {}", a, b, sum);
+    sum
+}
@@ -0,0 +1,9 @@
+pub fn main() {
+    let (prove, verify) = guest::build_add();
+
+    let (output, proof) = prove(3, 4).unwrap();
+    let is_valid = verify(proof);
+
+    println!("output: {}", output);
+    println!("valid: {}", is_valid);
+}
@@ -1,16 +1,11 @@
 #![cfg_attr(feature = "guest", no_std)]
 #![no_main]
 
-use sha2::{Digest, Sha256};
-
 #[jolt::provable]
 fn sha2_chain(input: [u8; 32], num_iters: u32) -> [u8; 32] {
     let mut hash = input;
     for _ in 0..num_iters {
-        let mut hasher = Sha256::new();
-        hasher.update(hash);
-        let res = &hasher.finalize();
-        hash = Into::<[u8; 32]>::into(*res);
+        hash = jolt::sha256(&hash);
     }
 
     hash
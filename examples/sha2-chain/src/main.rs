@@ -4,7 +4,7 @@ pub fn main() {
     let input = [5u8; 32];
     let iters = 100;
     let native_output = guest::sha2_chain(input, iters);
-    let (output, proof) = prove_sha2_chain(input, iters);
+    let (output, proof) = prove_sha2_chain(input, iters).unwrap();
     let is_valid = verify_sha2_chain(proof);
 
     assert_eq!(output, native_output, "output mismatch");
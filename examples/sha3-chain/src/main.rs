@@ -3,7 +3,7 @@ pub fn main() {
 
     let input = [5u8; 32];
     let iters = 100;
-    let (output, proof) = prove_sha3_chain(input, iters);
+    let (output, proof) = prove_sha3_chain(input, iters).unwrap();
     let is_valid = verify_sha3_chain(proof);
 
     println!("output: {}", hex::encode(output));
@@ -1,7 +1,7 @@
 pub fn main() {
     let (prove, verify) = guest::build_int_to_string();
 
-    let (output, proof) = prove(81);
+    let (output, proof) = prove(81).unwrap();
     let is_valid = verify(proof);
 
     println!("int to string output: {:?}", output);
@@ -9,7 +9,7 @@ pub fn main() {
 
     let (prove, verify) = guest::build_string_concat();
 
-    let (output, proof) = prove(20);
+    let (output, proof) = prove(20).unwrap();
     let is_valid = verify(proof);
 
     println!("string concat output: {:?}", output);
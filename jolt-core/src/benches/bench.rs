@@ -106,8 +106,8 @@ where
     program.set_input(input);
 
     let task = move || {
-        let (bytecode, memory_init) = program.decode();
-        let (io_device, trace, circuit_flags) = program.trace();
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
 
         let preprocessing: crate::jolt::vm::JoltPreprocessing<F, PCS> =
             RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 22);
@@ -160,8 +160,8 @@ where
     program.set_input(&1024u32);
 
     let task = move || {
-        let (bytecode, memory_init) = program.decode();
-        let (io_device, trace, circuit_flags) = program.trace();
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
 
         let preprocessing: crate::jolt::vm::JoltPreprocessing<F, PCS> =
             RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 22);
@@ -6,6 +6,7 @@ use tracer::{ELFInstruction, JoltDevice, RVTraceRow, RV32IM};
 use crate::{
     field::JoltField,
     jolt::vm::{rv32i_vm::RV32I, JoltTraceStep},
+    poly::commitment::commitment_scheme::{CommitShape, CommitmentScheme},
 };
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -50,3 +51,38 @@ impl ProgramSummary {
         Ok(())
     }
 }
+
+/// Cost of proving a guest execution, estimated by `Program::estimate_cost` from the
+/// trace alone, i.e. without running the prover.
+#[derive(Clone, Debug)]
+pub struct CostEstimate {
+    /// Number of Jolt trace steps, i.e. after virtual sequences are expanded.
+    pub trace_length: usize,
+    pub padded_trace_length: usize,
+    /// Number of bytecode rows, i.e. after virtual sequences are expanded.
+    pub bytecode_size: usize,
+    /// Highest RAM address read or written by the guest, or 0 if it accessed no RAM.
+    pub max_address: u64,
+    /// Size of the read-write memory committed to by the prover, including registers
+    /// and program I/O.
+    pub memory_size: usize,
+    /// Number of lookups into each subtable of `RV32ISubtables`, in descending order.
+    pub subtable_lookups: Vec<(&'static str, usize)>,
+    /// Shapes of the polynomials committed to when proving this execution.
+    pub commitment_shapes: Vec<CommitShape>,
+}
+
+impl CostEstimate {
+    pub fn num_lookups(&self) -> usize {
+        self.subtable_lookups.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Size in bytes of a commitment to a polynomial of each of `commitment_shapes`
+    /// under the commitment scheme `PCS`.
+    pub fn commitment_sizes<PCS: CommitmentScheme>(&self) -> Vec<usize> {
+        self.commitment_shapes
+            .iter()
+            .map(PCS::commitment_size)
+            .collect()
+    }
+}
@@ -8,37 +8,62 @@ use std::{
     process::Command,
 };
 
+use ark_bn254::{Fr, G1Projective};
 use postcard;
 use rayon::prelude::*;
 use serde::Serialize;
 
 use common::{
     constants::{
-        DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
+        memory_address_to_witness_index, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE,
+        DEFAULT_MAX_TRACE_LENGTH, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
     },
-    rv_trace::{JoltDevice, NUM_CIRCUIT_FLAGS},
+    rv_trace::{JoltDevice, MemoryState, RVTraceRow, NUM_CIRCUIT_FLAGS},
 };
-use strum::EnumCount;
-pub use tracer::ELFInstruction;
+use strum::{EnumCount, IntoEnumIterator};
+pub use tracer::{Debugger, ELFInstruction, StopReason, TracerError};
 
 use crate::{
     field::JoltField,
     jolt::{
         instruction::{
-            div::DIVInstruction, divu::DIVUInstruction, mulh::MULHInstruction,
-            mulhsu::MULHSUInstruction, rem::REMInstruction, remu::REMUInstruction,
-            VirtualInstructionSequence,
+            atomic::{
+                AMOADDWInstruction, AMOANDWInstruction, AMOMAXUWInstruction, AMOMAXWInstruction,
+                AMOMINUWInstruction, AMOMINWInstruction, AMOORWInstruction, AMOSWAPWInstruction,
+                AMOXORWInstruction, LRWInstruction, SCWInstruction,
+            },
+            div::DIVInstruction,
+            divu::DIVUInstruction,
+            keccak::KeccakFInstruction,
+            mulh::MULHInstruction,
+            mulhsu::MULHSUInstruction,
+            rem::REMInstruction,
+            remu::REMUInstruction,
+            sha256::SHA256CompressInstruction,
+            JoltInstruction, JoltInstructionSet, VirtualInstructionSequence,
+        },
+        subtable::JoltSubtableSet,
+        vm::{
+            bytecode::{BytecodePolynomials, BytecodeRow},
+            instruction_lookups::{InstructionLookupsPreprocessing, InstructionLookupsProof},
+            read_write_memory::ReadWriteMemory,
+            rv32i_vm::{RV32ISubtables, C, M, RV32I},
+            timestamp_range_check::TimestampValidityProof,
+            JoltTraceStep,
         },
-        vm::{bytecode::BytecodeRow, rv32i_vm::RV32I, JoltTraceStep},
     },
+    poly::commitment::hyrax::HyraxScheme,
+    r1cs::jolt_constraints::NUM_INSTRUCTION_FLAGS,
     utils::thread::unsafe_allocate_zero_vec,
 };
 
-use self::analyze::ProgramSummary;
+use self::analyze::{CostEstimate, ProgramSummary};
+use self::profile::{trace_steps, GuestProfile, Profiler};
 #[cfg(not(target_arch = "wasm32"))]
 use self::toolchain::{install_no_std_toolchain, install_toolchain};
 
 pub mod analyze;
+pub mod profile;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod toolchain;
 
@@ -51,8 +76,11 @@ pub struct Program {
     stack_size: u64,
     max_input_size: u64,
     max_output_size: u64,
+    max_cycles: u64,
     std: bool,
     pub elf: Option<PathBuf>,
+    /// Contents of a prebuilt guest ELF, see [`Program::from_elf_bytes`].
+    elf_contents: Option<Vec<u8>>,
 }
 
 impl Program {
@@ -65,11 +93,22 @@ impl Program {
             stack_size: DEFAULT_STACK_SIZE,
             max_input_size: DEFAULT_MAX_INPUT_SIZE,
             max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+            max_cycles: DEFAULT_MAX_TRACE_LENGTH,
             std: false,
             elf: None,
+            elf_contents: None,
         }
     }
 
+    /// Creates a program from an already compiled guest ELF. Such a program is never
+    /// built, so neither cargo nor the Jolt toolchain is needed to trace it. The input
+    /// and output sizes must be set to the values the guest was compiled with.
+    pub fn from_elf_bytes(elf: &[u8]) -> Self {
+        let mut program = Self::new("");
+        program.elf_contents = Some(elf.to_vec());
+        program
+    }
+
     pub fn set_std(&mut self, std: bool) {
         self.std = std;
     }
@@ -83,6 +122,11 @@ impl Program {
         self.input.append(&mut serialized);
     }
 
+    /// Appends already postcard-encoded input bytes, e.g. inputs read from a file.
+    pub fn set_input_bytes(&mut self, input: &[u8]) {
+        self.input.extend_from_slice(input);
+    }
+
     pub fn set_memory_size(&mut self, len: u64) {
         self.memory_size = len;
     }
@@ -99,9 +143,14 @@ impl Program {
         self.max_output_size = size;
     }
 
+    /// Sets the number of cycles the guest may run for before tracing is aborted.
+    pub fn set_max_cycles(&mut self, max_cycles: u64) {
+        self.max_cycles = max_cycles;
+    }
+
     #[tracing::instrument(skip_all, name = "Program::build")]
     pub fn build(&mut self) {
-        if self.elf.is_none() {
+        if self.elf.is_none() && self.elf_contents.is_none() {
             #[cfg(not(target_arch = "wasm32"))]
             install_toolchain().unwrap();
             #[cfg(not(target_arch = "wasm32"))]
@@ -109,19 +158,17 @@ impl Program {
 
             self.save_linker();
 
-            let rust_flags = [
-                "-C",
-                &format!("link-arg=-T{}", self.linker_path()),
-                "-C",
-                "passes=lower-atomic",
-                "-C",
-                "panic=abort",
-            ];
+            let linker_arg = format!("link-arg=-T{}", self.linker_path());
+            let mut rust_flags = vec!["-C", &linker_arg, "-C", "panic=abort"];
 
+            // The std toolchain targets RV32IM, so std guests still lower atomics to plain
+            // loads and stores and never execute RV32A instructions. no_std guests target
+            // RV32IMAC, whose core library is built from source.
             let toolchain = if self.std {
+                rust_flags.extend(["-C", "passes=lower-atomic"]);
                 "riscv32im-jolt-zkvm-elf"
             } else {
-                "riscv32im-unknown-none-elf"
+                "riscv32imac-unknown-none-elf"
             };
 
             let mut envs = vec![("CARGO_ENCODED_RUSTFLAGS", rust_flags.join("\x1f"))];
@@ -140,20 +187,25 @@ impl Program {
                 self.func.as_ref().unwrap_or(&"".to_string())
             );
 
+            let mut args = vec![
+                "build",
+                "--release",
+                "--features",
+                "guest",
+                "-p",
+                &self.guest,
+                "--target-dir",
+                &target,
+                "--target",
+                toolchain,
+            ];
+            if !self.std {
+                args.push("-Zbuild-std=core,alloc");
+            }
+
             let output = Command::new("cargo")
                 .envs(envs)
-                .args([
-                    "build",
-                    "--release",
-                    "--features",
-                    "guest",
-                    "-p",
-                    &self.guest,
-                    "--target-dir",
-                    &target,
-                    "--target",
-                    toolchain,
-                ])
+                .args(args)
                 .output()
                 .expect("failed to build guest");
 
@@ -167,49 +219,65 @@ impl Program {
         }
     }
 
-    pub fn decode(&mut self) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
+    pub fn decode(&mut self) -> Result<(Vec<ELFInstruction>, Vec<(u64, u8)>), TracerError> {
+        let elf_contents = self.elf_contents()?;
+        tracer::decode(&elf_contents)
+    }
+
+    /// Returns the guest ELF, building the guest first unless it was created with
+    /// [`Program::from_elf_bytes`].
+    fn elf_contents(&mut self) -> Result<Vec<u8>, TracerError> {
+        if let Some(elf_contents) = &self.elf_contents {
+            return Ok(elf_contents.clone());
+        }
         self.build();
         let elf = self.elf.as_ref().unwrap();
-        let mut elf_file = File::open(elf).unwrap();
+        let mut elf_file = File::open(elf)?;
         let mut elf_contents = Vec::new();
-        elf_file.read_to_end(&mut elf_contents).unwrap();
-        tracer::decode(&elf_contents)
+        elf_file.read_to_end(&mut elf_contents)?;
+        Ok(elf_contents)
     }
 
-    // TODO(moodlezoup): Make this generic over InstructionSet
+    /// Runs the guest, returning its program IO, its trace, the circuit flags of the trace
+    /// and the bytes it printed with `jolt::print!`. The printed bytes are not part of the
+    /// program IO, so they are not disclosed by a proof of the execution.
+    /// The custom instructions of `InstructionSet` are bound to their encodings while tracing.
     #[tracing::instrument(skip_all, name = "Program::trace")]
-    pub fn trace<F: JoltField>(mut self) -> (JoltDevice, Vec<JoltTraceStep<RV32I>>, Vec<F>) {
-        self.build();
-        let elf = self.elf.unwrap();
-        let (raw_trace, io_device) =
-            tracer::trace(&elf, &self.input, self.max_input_size, self.max_output_size);
-
-        let trace: Vec<_> = raw_trace
-            .into_par_iter()
-            .flat_map(|row| match row.instruction.opcode {
-                tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::DIV => DIVInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::REM => REMInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_trace(row),
-                _ => vec![row],
-            })
-            .map(|row| {
-                let instruction_lookup = if let Ok(jolt_instruction) = RV32I::try_from(&row) {
-                    Some(jolt_instruction)
-                } else {
-                    // Instruction does not use lookups
-                    None
-                };
-
-                JoltTraceStep {
-                    instruction_lookup,
-                    bytecode_row: BytecodeRow::from_instruction::<RV32I>(&row.instruction),
-                    memory_ops: (&row).into(),
+    pub fn trace<F: JoltField, InstructionSet>(
+        mut self,
+    ) -> Result<
+        (
+            JoltDevice,
+            Vec<JoltTraceStep<InstructionSet>>,
+            Vec<F>,
+            Vec<u8>,
+        ),
+        TracerError,
+    >
+    where
+        InstructionSet: JoltInstructionSet + for<'a> TryFrom<&'a RVTraceRow>,
+    {
+        let elf_contents = self.elf_contents()?;
+
+        // Rows are processed in chunks as the tracer emits them, so the raw trace is never
+        // held in memory alongside the processed one.
+        let mut trace = Vec::new();
+        let mut rows = Vec::with_capacity(TRACE_CHUNK_SIZE);
+        let (io_device, log) = tracer::trace_with_sink(
+            &elf_contents,
+            &self.input,
+            self.max_input_size,
+            self.max_output_size,
+            self.max_cycles,
+            &InstructionSet::custom_instructions(),
+            |row| {
+                rows.push(row);
+                if rows.len() == TRACE_CHUNK_SIZE {
+                    append_trace_steps(&mut rows, &mut trace);
                 }
-            })
-            .collect();
+            },
+        )?;
+        append_trace_steps(&mut rows, &mut trace);
 
         let padded_trace_len = trace.len().next_power_of_two();
 
@@ -219,37 +287,172 @@ impl Program {
             .enumerate()
             .for_each(|(flag_index, chunk)| {
                 chunk.iter_mut().zip(trace.iter()).for_each(|(flag, row)| {
-                    let packed_circuit_flags = row.bytecode_row.bitflags >> RV32I::COUNT;
+                    let packed_circuit_flags = row.bytecode_row.bitflags >> NUM_INSTRUCTION_FLAGS;
                     // Check if the flag is set in the packed representation
                     if (packed_circuit_flags >> (NUM_CIRCUIT_FLAGS - flag_index - 1)) & 1 != 0 {
                         *flag = F::one();
                     }
                 });
             });
-        (io_device, trace, circuit_flag_trace)
+        Ok((io_device, trace, circuit_flag_trace, log))
     }
 
-    pub fn trace_analyze<F: JoltField>(mut self) -> ProgramSummary {
-        self.build();
-        let elf = self.elf.as_ref().unwrap();
-        let (raw_trace, _) =
-            tracer::trace(elf, &self.input, self.max_input_size, self.max_output_size);
-
-        let (bytecode, memory_init) = self.decode();
-        let (io_device, processed_trace, circuit_flags) = self.trace();
+    pub fn trace_analyze<F: JoltField>(mut self) -> Result<ProgramSummary, TracerError> {
+        let elf_contents = self.elf_contents()?;
+        let (raw_trace, _) = tracer::trace_from_bytes(
+            &elf_contents,
+            &self.input,
+            self.max_input_size,
+            self.max_output_size,
+            self.max_cycles,
+        )?;
+
+        let (bytecode, memory_init) = self.decode()?;
+        let (io_device, processed_trace, circuit_flags, _) = self.trace()?;
         let circuit_flags: Vec<bool> = circuit_flags
             .into_iter()
             .map(|flag: F| flag.is_one())
             .collect();
 
-        ProgramSummary {
+        Ok(ProgramSummary {
             raw_trace,
             bytecode,
             memory_init,
             io_device,
             processed_trace,
             circuit_flags,
+        })
+    }
+
+    /// Runs only the tracer and estimates the cost of proving the execution, see
+    /// [`CostEstimate`].
+    pub fn estimate_cost(mut self) -> Result<CostEstimate, TracerError> {
+        // Commitment shapes don't depend on the field or the commitment scheme
+        type ShapeScheme = HyraxScheme<G1Projective>;
+
+        let elf_contents = self.elf_contents()?;
+        let (bytecode, _) = tracer::decode(&elf_contents)?;
+        let bytecode_size = bytecode
+            .iter()
+            .map(|instruction| trace_steps(instruction.opcode))
+            .sum();
+
+        let mut trace_length = 0;
+        let mut max_address = 0;
+        let mut instruction_counts = vec![0; RV32I::COUNT];
+        let mut rows = Vec::with_capacity(TRACE_CHUNK_SIZE);
+        let mut steps = Vec::with_capacity(TRACE_CHUNK_SIZE);
+        let mut count_instructions = |rows: &mut Vec<RVTraceRow>| {
+            append_trace_steps(rows, &mut steps);
+            trace_length += steps.len();
+            for step in steps.drain(..) {
+                if let Some(instruction) = step.instruction_lookup {
+                    instruction_counts[RV32I::enum_index(&instruction)] += 1;
+                }
+            }
+        };
+        let (io_device, _) = tracer::trace_with_sink(
+            &elf_contents,
+            &self.input,
+            self.max_input_size,
+            self.max_output_size,
+            self.max_cycles,
+            &[],
+            |row| {
+                if let Some(
+                    MemoryState::Read { address, .. } | MemoryState::Write { address, .. },
+                ) = row.memory_state
+                {
+                    max_address = max_address.max(address);
+                }
+                rows.push(row);
+                if rows.len() == TRACE_CHUNK_SIZE {
+                    count_instructions(&mut rows);
+                }
+            },
+        )?;
+        count_instructions(&mut rows);
+
+        let mut lookups = vec![0; RV32ISubtables::<Fr>::COUNT];
+        for instruction in RV32I::iter() {
+            let count = instruction_counts[RV32I::enum_index(&instruction)];
+            for (subtable, indices) in instruction.subtables::<Fr>(C, M) {
+                lookups[RV32ISubtables::enum_index(subtable)] += count * indices.len();
+            }
         }
+        let mut subtable_lookups: Vec<(&'static str, usize)> = RV32ISubtables::<Fr>::iter()
+            .map(|subtable| <&'static str>::from(&subtable))
+            .zip(lookups)
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        subtable_lookups.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        // Mirrors the memory size computed in `ReadWriteMemory::new`
+        let ram_witness_offset = io_device.memory_layout.ram_witness_offset;
+        let max_witness_index = if max_address == 0 {
+            0
+        } else {
+            memory_address_to_witness_index(max_address, ram_witness_offset) as u64
+        };
+        let memory_size = (ram_witness_offset + max_witness_index).next_power_of_two() as usize;
+
+        let padded_trace_length = trace_length.next_power_of_two();
+        let instruction_lookups_preprocessing =
+            InstructionLookupsPreprocessing::preprocess::<C, M, RV32I, RV32ISubtables<Fr>>();
+        let commitment_shapes = [
+            BytecodePolynomials::<Fr, ShapeScheme>::commit_shapes(
+                bytecode_size,
+                padded_trace_length,
+            ),
+            ReadWriteMemory::<Fr, ShapeScheme>::commitment_shapes(
+                memory_size,
+                padded_trace_length,
+            ),
+            TimestampValidityProof::<Fr, ShapeScheme>::commitment_shapes(padded_trace_length),
+            InstructionLookupsProof::<C, M, Fr, ShapeScheme, RV32I, RV32ISubtables<Fr>>::commitment_shapes(
+                &instruction_lookups_preprocessing,
+                padded_trace_length,
+            ),
+        ]
+        .concat();
+
+        Ok(CostEstimate {
+            trace_length,
+            padded_trace_length,
+            bytecode_size,
+            max_address,
+            memory_size,
+            subtable_lookups,
+            commitment_shapes,
+        })
+    }
+
+    /// Runs the guest and attributes its cycles to the functions in the guest's symbol
+    /// table, see [`GuestProfile`].
+    pub fn profile(mut self) -> Result<GuestProfile, TracerError> {
+        let elf_contents = self.elf_contents()?;
+        let mut profiler = Profiler::new(tracer::function_symbols(&elf_contents)?);
+        tracer::trace_with_sink(
+            &elf_contents,
+            &self.input,
+            self.max_input_size,
+            self.max_output_size,
+            self.max_cycles,
+            &[],
+            |row| profiler.record(&row),
+        )?;
+        Ok(profiler.finish())
+    }
+
+    /// Loads the guest into a [`Debugger`] with this program's inputs, without running it.
+    pub fn debugger(mut self) -> Result<Debugger, TracerError> {
+        let elf_contents = self.elf_contents()?;
+        Debugger::new(
+            &elf_contents,
+            &self.input,
+            self.max_input_size,
+            self.max_output_size,
+        )
     }
 
     fn save_linker(&self) {
@@ -272,6 +475,61 @@ impl Program {
     }
 }
 
+/// Number of raw trace rows buffered by `Program::trace` before they are processed.
+const TRACE_CHUNK_SIZE: usize = 1 << 16;
+
+/// Expands virtual instruction sequences in `rows` and appends the resulting steps to
+/// `trace`, leaving `rows` empty.
+fn append_trace_steps<InstructionSet>(
+    rows: &mut Vec<RVTraceRow>,
+    trace: &mut Vec<JoltTraceStep<InstructionSet>>,
+) where
+    InstructionSet: JoltInstructionSet + for<'a> TryFrom<&'a RVTraceRow>,
+{
+    trace.par_extend(
+        rows.par_drain(..)
+            .flat_map(|row| match row.instruction.opcode {
+                tracer::RV32IM::MULH => MULHInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::DIV => DIVInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::DIVU => DIVUInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::REM => REMInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::REMU => REMUInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::LR_W => LRWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::SC_W => SCWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOSWAP_W => AMOSWAPWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOADD_W => AMOADDWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOXOR_W => AMOXORWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOAND_W => AMOANDWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOOR_W => AMOORWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOMIN_W => AMOMINWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOMAX_W => AMOMAXWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOMINU_W => AMOMINUWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::AMOMAXU_W => AMOMAXUWInstruction::<32>::expand_trace(row),
+                tracer::RV32IM::SHA256_COMPRESS => {
+                    SHA256CompressInstruction::<32>::expand_trace(row)
+                }
+                tracer::RV32IM::KECCAK_F => KeccakFInstruction::<32>::expand_trace(row),
+                _ => vec![row],
+            })
+            .map(|row| {
+                let instruction_lookup =
+                    if let Ok(jolt_instruction) = InstructionSet::try_from(&row) {
+                        Some(jolt_instruction)
+                    } else {
+                        // Instruction does not use lookups
+                        None
+                    };
+
+                JoltTraceStep {
+                    instruction_lookup,
+                    bytecode_row: BytecodeRow::from_instruction::<InstructionSet>(&row.instruction),
+                    memory_ops: (&row).into(),
+                }
+            }),
+    );
+}
+
 const LINKER_SCRIPT_TEMPLATE: &str = r#"
 MEMORY {
   program (rwx) : ORIGIN = 0x80000000, LENGTH = {MEMORY_SIZE}
@@ -0,0 +1,275 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use rustc_demangle::demangle;
+use tracer::{FunctionSymbol, RVTraceRow, RV32IM};
+
+use crate::jolt::instruction::{
+    atomic::{
+        AMOADDWInstruction, AMOANDWInstruction, AMOMAXUWInstruction, AMOMAXWInstruction,
+        AMOMINUWInstruction, AMOMINWInstruction, AMOORWInstruction, AMOSWAPWInstruction,
+        AMOXORWInstruction, LRWInstruction, SCWInstruction,
+    },
+    div::DIVInstruction,
+    divu::DIVUInstruction,
+    keccak::KeccakFInstruction,
+    mulh::MULHInstruction,
+    mulhsu::MULHSUInstruction,
+    rem::REMInstruction,
+    remu::REMUInstruction,
+    sha256::SHA256CompressInstruction,
+    VirtualInstructionSequence,
+};
+
+const UNKNOWN_FUNCTION: &str = "[unknown]";
+
+/// Number of Jolt trace steps an instruction expands to once its virtual sequence, if
+/// any, is inlined.
+pub(crate) fn trace_steps(opcode: RV32IM) -> usize {
+    match opcode {
+        RV32IM::MULH => MULHInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::MULHSU => MULHSUInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::DIV => DIVInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::DIVU => DIVUInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::REM => REMInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::REMU => REMUInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::LR_W => LRWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::SC_W => SCWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOSWAP_W => AMOSWAPWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOADD_W => AMOADDWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOXOR_W => AMOXORWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOAND_W => AMOANDWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOOR_W => AMOORWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOMIN_W => AMOMINWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOMAX_W => AMOMAXWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOMINU_W => AMOMINUWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::AMOMAXU_W => AMOMAXUWInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::SHA256_COMPRESS => SHA256CompressInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::KECCAK_F => KeccakFInstruction::<32>::SEQUENCE_LENGTH,
+        _ => 1,
+    }
+}
+
+/// Cycles of a guest execution attributed to the functions of the guest, as produced by
+/// `Program::profile`. Cycles are counted in Jolt trace steps, i.e. after virtual
+/// sequences are expanded, so they add up to the (unpadded) trace length.
+pub struct GuestProfile {
+    functions: Vec<String>,
+    /// Cycles spent in each call stack, given as indices into `functions` with the
+    /// outermost frame first.
+    stacks: HashMap<Vec<usize>, usize>,
+}
+
+impl GuestProfile {
+    pub fn total_cycles(&self) -> usize {
+        self.stacks.values().sum()
+    }
+
+    /// Cycles spent in each function itself, excluding its callees, in descending order.
+    pub fn self_cycles(&self) -> Vec<(&str, usize)> {
+        let mut cycles = vec![0; self.functions.len()];
+        for (stack, count) in self.stacks.iter() {
+            cycles[*stack.last().unwrap()] += count;
+        }
+        self.sorted(cycles)
+    }
+
+    /// Cycles spent in each function including its callees, in descending order.
+    pub fn total_cycles_by_function(&self) -> Vec<(&str, usize)> {
+        let mut cycles = vec![0; self.functions.len()];
+        for (stack, count) in self.stacks.iter() {
+            let mut seen = stack.clone();
+            seen.sort_unstable();
+            seen.dedup();
+            for function in seen {
+                cycles[function] += count;
+            }
+        }
+        self.sorted(cycles)
+    }
+
+    fn sorted(&self, cycles: Vec<usize>) -> Vec<(&str, usize)> {
+        let mut cycles: Vec<_> = self
+            .functions
+            .iter()
+            .map(String::as_str)
+            .zip(cycles)
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        cycles.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        cycles
+    }
+
+    /// Writes the profile in the folded stack format understood by `flamegraph.pl` and
+    /// `inferno-flamegraph`: one `outer;inner cycles` line per call stack.
+    pub fn write_folded<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut lines: Vec<_> = self
+            .stacks
+            .iter()
+            .map(|(stack, count)| {
+                let frames: Vec<_> = stack
+                    .iter()
+                    .map(|function| self.functions[*function].as_str())
+                    .collect();
+                (frames.join(";"), count)
+            })
+            .collect();
+        lines.sort();
+
+        let mut file = BufWriter::new(File::create(path)?);
+        for (frames, count) in lines {
+            writeln!(file, "{} {}", frames, count)?;
+        }
+        file.flush()
+    }
+}
+
+/// Attributes trace rows to guest functions as they are emitted by the tracer.
+///
+/// Call stacks are reconstructed from the trace itself: a `jal`/`jalr` that writes the
+/// return address register is a call and `jalr x0, 0(ra)` is a return. Tail calls and
+/// other jumps between functions replace the innermost frame.
+pub(crate) struct Profiler {
+    symbols: Vec<FunctionSymbol>,
+    functions: Vec<String>,
+    stack: Vec<usize>,
+    stacks: HashMap<Vec<usize>, usize>,
+}
+
+impl Profiler {
+    pub fn new(symbols: Vec<FunctionSymbol>) -> Self {
+        let mut functions: Vec<_> = symbols
+            .iter()
+            // `;` separates frames in the folded format, but can appear in array types
+            .map(|symbol| format!("{:#}", demangle(&symbol.name)).replace(';', ":"))
+            .collect();
+        functions.push(UNKNOWN_FUNCTION.to_string());
+
+        Self {
+            symbols,
+            functions,
+            stack: Vec::new(),
+            stacks: HashMap::new(),
+        }
+    }
+
+    /// Index of the function containing `address`, or of the unknown function.
+    fn function_at(&self, address: u64) -> usize {
+        let index = self
+            .symbols
+            .partition_point(|symbol| symbol.address <= address);
+        match index.checked_sub(1) {
+            Some(index)
+                if self.symbols[index].size == 0
+                    || address < self.symbols[index].address + self.symbols[index].size =>
+            {
+                index
+            }
+            _ => self.symbols.len(),
+        }
+    }
+
+    pub fn record(&mut self, row: &RVTraceRow) {
+        let instruction = &row.instruction;
+        let function = self.function_at(instruction.address);
+        match self.stack.last_mut() {
+            Some(frame) => *frame = function,
+            None => self.stack.push(function),
+        }
+
+        let cycles = trace_steps(instruction.opcode);
+        match self.stacks.get_mut(self.stack.as_slice()) {
+            Some(count) => *count += cycles,
+            None => {
+                self.stacks.insert(self.stack.clone(), cycles);
+            }
+        }
+
+        let is_jump = matches!(instruction.opcode, RV32IM::JAL | RV32IM::JALR);
+        if is_jump && instruction.rd == Some(1) {
+            // The callee is filled in by the next instruction
+            self.stack.push(function);
+        } else if instruction.opcode == RV32IM::JALR
+            && instruction.rd == Some(0)
+            && instruction.rs1 == Some(1)
+            && self.stack.len() > 1
+        {
+            self.stack.pop();
+        }
+    }
+
+    pub fn finish(self) -> GuestProfile {
+        GuestProfile {
+            functions: self.functions,
+            stacks: self.stacks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracer::{ELFInstruction, RegisterState};
+
+    fn row(address: u64, opcode: RV32IM, rd: Option<u64>, rs1: Option<u64>) -> RVTraceRow {
+        RVTraceRow {
+            instruction: ELFInstruction {
+                address,
+                opcode,
+                rs1,
+                rs2: None,
+                rd,
+                imm: None,
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            },
+            register_state: RegisterState::default(),
+            memory_state: None,
+            advice_value: None,
+            precompile_input: None,
+        }
+    }
+
+    fn symbol(name: &str, address: u64, size: u64) -> FunctionSymbol {
+        FunctionSymbol {
+            name: name.to_string(),
+            address,
+            size,
+        }
+    }
+
+    #[test]
+    fn attributes_cycles_to_call_stacks() {
+        let mut profiler =
+            Profiler::new(vec![symbol("main", 0x100, 0x10), symbol("f", 0x200, 0x10)]);
+        // main calls f, which executes a DIV and returns
+        profiler.record(&row(0x100, RV32IM::ADDI, Some(5), Some(5)));
+        profiler.record(&row(0x104, RV32IM::JAL, Some(1), None));
+        profiler.record(&row(0x200, RV32IM::DIV, Some(5), Some(5)));
+        profiler.record(&row(0x204, RV32IM::JALR, Some(0), Some(1)));
+        profiler.record(&row(0x108, RV32IM::ADDI, Some(5), Some(5)));
+        // a jump outside of any symbol
+        profiler.record(&row(0x300, RV32IM::ADDI, Some(5), Some(5)));
+        let profile = profiler.finish();
+
+        let div_cycles = DIVInstruction::<32>::SEQUENCE_LENGTH;
+        assert_eq!(profile.total_cycles(), 5 + div_cycles);
+        assert_eq!(
+            profile.self_cycles(),
+            vec![("f", 1 + div_cycles), ("main", 3), (UNKNOWN_FUNCTION, 1)]
+        );
+        assert_eq!(
+            profile.total_cycles_by_function(),
+            vec![
+                ("main", 4 + div_cycles),
+                ("f", 1 + div_cycles),
+                (UNKNOWN_FUNCTION, 1)
+            ]
+        );
+        assert_eq!(profile.stacks.get(&vec![0, 1]), Some(&(1 + div_cycles)));
+    }
+}
@@ -0,0 +1,570 @@
+//! Virtual sequences for the RV32A atomic instructions. Guests run on a single hart without
+//! interrupts, so an atomic read-modify-write can be proven as a load, some ALU lookups and
+//! a store of the same word.
+//!
+//! No constraints specific to atomics are needed. The rows of a virtual sequence are
+//! consecutive in the trace and `virtual_sequence_constraint` in `jolt_constraints.rs` ties
+//! each of them to the next bytecode row of the same expansion, so no other memory access
+//! can fall between the load and the store. Those are checked by the read-write memory
+//! argument like any LW and SW, and the ALU steps by their lookups.
+//!
+//! The reservation of LR.W is kept in `RESERVATION_REGISTER`, so the outcome of SC.W is
+//! proven rather than taken from the prover: it succeeds only if rs1 holds the reserved
+//! address, and stores the word it loaded back otherwise.
+
+use common::constants::{virtual_register_index, RESERVATION_REGISTER};
+use tracer::{MemoryState, RVTraceRow, RV32IM};
+
+use super::sequence_builder::{Operand, SequenceBuilder};
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{
+    add::ADDInstruction, and::ANDInstruction, or::ORInstruction, slt::SLTInstruction,
+    sltu::SLTUInstruction, sub::SUBInstruction, xor::XORInstruction, JoltInstruction,
+};
+
+/// The word loaded by an atomic instruction. The tracer records it as the memory read of
+/// LR.W, and as advice for AMOs, whose memory state is the write. Zero when expanding
+/// bytecode, which has no trace values.
+fn loaded_word(trace_row: &RVTraceRow) -> u64 {
+    match (trace_row.advice_value, &trace_row.memory_state) {
+        (Some(value), _) => value,
+        (None, Some(MemoryState::Read { value, .. })) => *value,
+        _ => 0,
+    }
+}
+
+/// Expands an AMO into a load of the word at `rs1` into a virtual register, the
+/// instructions emitted by `update` to compute the new word from the loaded word and `rs2`,
+/// a store of the new word, and a move of the loaded word into `rd`. `rd` is written last,
+/// so it may alias `rs1` or `rs2`.
+fn amo_virtual_trace(
+    trace_row: RVTraceRow,
+    sequence_length: usize,
+    update: impl FnOnce(&mut SequenceBuilder, Operand, Operand) -> Operand,
+) -> Vec<RVTraceRow> {
+    let base = (
+        trace_row.instruction.rs1,
+        trace_row.register_state.rs1_val.unwrap(),
+    );
+    let source = (
+        trace_row.instruction.rs2,
+        trace_row.register_state.rs2_val.unwrap(),
+    );
+    let v_loaded = Some(virtual_register_index(0));
+
+    let mut sequence = SequenceBuilder::new(&trace_row, sequence_length);
+    let loaded = sequence.load(base, 0, v_loaded, loaded_word(&trace_row));
+    let updated = update(&mut sequence, loaded, source);
+    sequence.store(base, 0, updated);
+    sequence.mov(loaded, trace_row.instruction.rd);
+    sequence.finish()
+}
+
+/// Emits a branchless select of `source` if `lhs < rhs` (as compared by `opcode`, SLT or
+/// SLTU) and of `loaded` otherwise, i.e. `loaded ^ ((loaded ^ source) & -(lhs < rhs))`.
+fn amo_select<const WORD_SIZE: usize>(
+    sequence: &mut SequenceBuilder,
+    opcode: RV32IM,
+    (lhs, rhs): (Operand, Operand),
+    loaded: Operand,
+    source: Operand,
+) -> Operand {
+    let v_condition = Some(virtual_register_index(1));
+    let v_mask = Some(virtual_register_index(2));
+    let v_difference = Some(virtual_register_index(3));
+    let v_updated = Some(virtual_register_index(4));
+
+    let condition = match opcode {
+        RV32IM::SLT => SLTInstruction::<WORD_SIZE>(lhs.1, rhs.1).lookup_entry(),
+        RV32IM::SLTU => SLTUInstruction::<WORD_SIZE>(lhs.1, rhs.1).lookup_entry(),
+        _ => unreachable!(),
+    };
+    let condition = sequence.alu(opcode, lhs, rhs, v_condition, condition);
+    let mask = SUBInstruction::<WORD_SIZE>(0, condition.1).lookup_entry();
+    let mask = sequence.alu(RV32IM::SUB, (Some(0), 0), condition, v_mask, mask);
+    let difference = XORInstruction::<WORD_SIZE>(loaded.1, source.1).lookup_entry();
+    let difference = sequence.alu(RV32IM::XOR, loaded, source, v_difference, difference);
+    let masked = ANDInstruction::<WORD_SIZE>(difference.1, mask.1).lookup_entry();
+    let masked = sequence.alu(RV32IM::AND, difference, mask, v_difference, masked);
+    let updated = XORInstruction::<WORD_SIZE>(loaded.1, masked.1).lookup_entry();
+    sequence.alu(RV32IM::XOR, loaded, masked, v_updated, updated)
+}
+
+/// LR.W, proven as a move of rs1 into the reservation register and a LW.
+pub struct LRWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for LRWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 2;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::LR_W);
+        let base = (
+            trace_row.instruction.rs1,
+            trace_row.register_state.rs1_val.unwrap(),
+        );
+
+        let mut sequence = SequenceBuilder::new(&trace_row, Self::SEQUENCE_LENGTH);
+        sequence.mov(base, Some(RESERVATION_REGISTER));
+        sequence.load(base, 0, trace_row.instruction.rd, loaded_word(&trace_row));
+        sequence.finish()
+    }
+
+    /// The value of rd given the loaded word `x`.
+    fn sequence_output(x: u64, _: u64) -> u64 {
+        x
+    }
+}
+
+/// SC.W. Stores rs2 and writes zero to rd if rs1 holds the reserved address, and otherwise
+/// stores the loaded word back and writes one to rd. Either way the reservation is cleared.
+pub struct SCWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for SCWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 10;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::SC_W);
+        let base = (
+            trace_row.instruction.rs1,
+            trace_row.register_state.rs1_val.unwrap(),
+        );
+        let source = (
+            trace_row.instruction.rs2,
+            trace_row.register_state.rs2_val.unwrap(),
+        );
+        // The tracer records the load as advice when the store succeeds, and the reserved
+        // address when it fails. Zero when expanding bytecode, which has no trace values.
+        let (reservation, loaded) = match (&trace_row.memory_state, trace_row.advice_value) {
+            (Some(MemoryState::Write { .. }), Some(loaded)) => (base.1, loaded),
+            (Some(MemoryState::Read { value, .. }), Some(reservation)) => (reservation, *value),
+            _ => (0, 0),
+        };
+        let reservation = (Some(RESERVATION_REGISTER), reservation);
+
+        let v_mismatch = Some(virtual_register_index(0));
+        let v_failed = Some(virtual_register_index(1));
+        let v_loaded = Some(virtual_register_index(2));
+        let v_mask = Some(virtual_register_index(3));
+        let v_updated = Some(virtual_register_index(4));
+
+        let mut sequence = SequenceBuilder::new(&trace_row, Self::SEQUENCE_LENGTH);
+        let mismatch = XORInstruction::<WORD_SIZE>(base.1, reservation.1).lookup_entry();
+        let mismatch = sequence.alu(RV32IM::XOR, base, reservation, v_mismatch, mismatch);
+        let failed = SLTUInstruction::<WORD_SIZE>(0, mismatch.1).lookup_entry();
+        let failed = sequence.alu(RV32IM::SLTU, (Some(0), 0), mismatch, v_failed, failed);
+        let loaded = sequence.load(base, 0, v_loaded, loaded);
+        // All ones if the store succeeds, zero otherwise
+        let mask = ADDInstruction::<WORD_SIZE>(failed.1, u32::MAX as u64).lookup_entry();
+        let mask = sequence.alu_imm(RV32IM::ADDI, failed, u32::MAX, v_mask, mask);
+        // loaded ^ ((loaded ^ rs2) & mask)
+        let difference = XORInstruction::<WORD_SIZE>(loaded.1, source.1).lookup_entry();
+        let difference = sequence.alu(RV32IM::XOR, loaded, source, v_updated, difference);
+        let masked = ANDInstruction::<WORD_SIZE>(difference.1, mask.1).lookup_entry();
+        let masked = sequence.alu(RV32IM::AND, difference, mask, v_updated, masked);
+        let updated = XORInstruction::<WORD_SIZE>(loaded.1, masked.1).lookup_entry();
+        let updated = sequence.alu(RV32IM::XOR, loaded, masked, v_updated, updated);
+        sequence.store(base, 0, updated);
+        sequence.mov((Some(0), 0), Some(RESERVATION_REGISTER));
+        sequence.mov(failed, trace_row.instruction.rd);
+        sequence.finish()
+    }
+
+    /// The value of rd given the reserved address `x` and rs1 `y`.
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        (x != y) as u64
+    }
+}
+
+/// AMOSWAP.W. For all AMOs, `sequence_output` gives the word stored given the loaded word
+/// `x` and rs2 `y`; rd receives the loaded word.
+pub struct AMOSWAPWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOSWAPWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 3;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOSWAP_W);
+        amo_virtual_trace(trace_row, Self::SEQUENCE_LENGTH, |_, _, source| source)
+    }
+
+    fn sequence_output(_: u64, y: u64) -> u64 {
+        y
+    }
+}
+
+pub struct AMOADDWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOADDWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 4;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOADD_W);
+        amo_virtual_trace(
+            trace_row,
+            Self::SEQUENCE_LENGTH,
+            |sequence, loaded, source| {
+                let v_updated = Some(virtual_register_index(1));
+                let updated = ADDInstruction::<WORD_SIZE>(loaded.1, source.1).lookup_entry();
+                sequence.alu(RV32IM::ADD, loaded, source, v_updated, updated)
+            },
+        )
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        ADDInstruction::<WORD_SIZE>(x, y).lookup_entry()
+    }
+}
+
+pub struct AMOXORWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOXORWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 4;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOXOR_W);
+        amo_virtual_trace(
+            trace_row,
+            Self::SEQUENCE_LENGTH,
+            |sequence, loaded, source| {
+                let v_updated = Some(virtual_register_index(1));
+                let updated = XORInstruction::<WORD_SIZE>(loaded.1, source.1).lookup_entry();
+                sequence.alu(RV32IM::XOR, loaded, source, v_updated, updated)
+            },
+        )
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        XORInstruction::<WORD_SIZE>(x, y).lookup_entry()
+    }
+}
+
+pub struct AMOANDWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOANDWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 4;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOAND_W);
+        amo_virtual_trace(
+            trace_row,
+            Self::SEQUENCE_LENGTH,
+            |sequence, loaded, source| {
+                let v_updated = Some(virtual_register_index(1));
+                let updated = ANDInstruction::<WORD_SIZE>(loaded.1, source.1).lookup_entry();
+                sequence.alu(RV32IM::AND, loaded, source, v_updated, updated)
+            },
+        )
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        ANDInstruction::<WORD_SIZE>(x, y).lookup_entry()
+    }
+}
+
+pub struct AMOORWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOORWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 4;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOOR_W);
+        amo_virtual_trace(
+            trace_row,
+            Self::SEQUENCE_LENGTH,
+            |sequence, loaded, source| {
+                let v_updated = Some(virtual_register_index(1));
+                let updated = ORInstruction::<WORD_SIZE>(loaded.1, source.1).lookup_entry();
+                sequence.alu(RV32IM::OR, loaded, source, v_updated, updated)
+            },
+        )
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        ORInstruction::<WORD_SIZE>(x, y).lookup_entry()
+    }
+}
+
+pub struct AMOMINWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOMINWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 8;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOMIN_W);
+        amo_virtual_trace(
+            trace_row,
+            Self::SEQUENCE_LENGTH,
+            |sequence, loaded, source| {
+                amo_select::<WORD_SIZE>(sequence, RV32IM::SLT, (source, loaded), loaded, source)
+            },
+        )
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        if SLTInstruction::<WORD_SIZE>(y, x).lookup_entry() == 1 {
+            y
+        } else {
+            x
+        }
+    }
+}
+
+pub struct AMOMAXWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOMAXWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 8;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOMAX_W);
+        amo_virtual_trace(
+            trace_row,
+            Self::SEQUENCE_LENGTH,
+            |sequence, loaded, source| {
+                amo_select::<WORD_SIZE>(sequence, RV32IM::SLT, (loaded, source), loaded, source)
+            },
+        )
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        if SLTInstruction::<WORD_SIZE>(x, y).lookup_entry() == 1 {
+            y
+        } else {
+            x
+        }
+    }
+}
+
+pub struct AMOMINUWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOMINUWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 8;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOMINU_W);
+        amo_virtual_trace(
+            trace_row,
+            Self::SEQUENCE_LENGTH,
+            |sequence, loaded, source| {
+                amo_select::<WORD_SIZE>(sequence, RV32IM::SLTU, (source, loaded), loaded, source)
+            },
+        )
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        x.min(y)
+    }
+}
+
+pub struct AMOMAXUWInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for AMOMAXUWInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 8;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::AMOMAXU_W);
+        amo_virtual_trace(
+            trace_row,
+            Self::SEQUENCE_LENGTH,
+            |sequence, loaded, source| {
+                amo_select::<WORD_SIZE>(sequence, RV32IM::SLTU, (loaded, source), loaded, source)
+            },
+        )
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        x.max(y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use ark_std::test_rng;
+    use common::constants::REGISTER_COUNT;
+    use rand_chacha::rand_core::RngCore;
+    use tracer::{ELFInstruction, RegisterState};
+
+    use super::*;
+    use crate::jolt::vm::rv32i_vm::RV32I;
+
+    /// Traces `opcode` the way the tracer does, on random registers (which may alias), a
+    /// random word in memory and a reservation that is either unset, of rs1's address or of
+    /// another address. Then executes its virtual sequence and checks that memory ends up
+    /// holding `sequence_output`, rd the loaded word (or whether SC.W failed), and the
+    /// reservation register the reservation left by the instruction.
+    fn atomic_virtual_sequence_test<I: VirtualInstructionSequence>(opcode: RV32IM) {
+        let mut rng = test_rng();
+        for _ in 0..64 {
+            let rs1 = 1 + rng.next_u64() % 31;
+            let rs2 = 1 + rng.next_u64() % 31;
+            let rd = rng.next_u64() % 32;
+            let address = 0x8000_0000 + 4 * (rng.next_u32() as u64 % 1024);
+            let loaded = rng.next_u32() as u64;
+            let reservation = match rng.next_u32() % 3 {
+                0 => 0,
+                1 => address,
+                _ => address ^ 4,
+            };
+
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            registers[rs2 as usize] = rng.next_u32() as u64;
+            registers[rs1 as usize] = address;
+            registers[RESERVATION_REGISTER as usize] = reservation;
+            let source = registers[rs2 as usize];
+            let mut memory = HashMap::from([(address, loaded)]);
+
+            let (stored, rd_value, reservation_after) = match opcode {
+                RV32IM::LR_W => (loaded, loaded, address),
+                RV32IM::SC_W if reservation == address => (source, 0, 0),
+                RV32IM::SC_W => (loaded, 1, 0),
+                _ => (I::sequence_output(loaded, source), loaded, reservation),
+            };
+            let (memory_state, advice_value) = match opcode {
+                RV32IM::LR_W => (
+                    MemoryState::Read {
+                        address,
+                        value: loaded,
+                    },
+                    None,
+                ),
+                RV32IM::SC_W if rd_value == 1 => (
+                    MemoryState::Read {
+                        address,
+                        value: loaded,
+                    },
+                    Some(reservation),
+                ),
+                _ => (
+                    MemoryState::Write {
+                        address,
+                        post_value: stored,
+                    },
+                    Some(loaded),
+                ),
+            };
+            let trace_row = RVTraceRow {
+                instruction: ELFInstruction {
+                    address: rng.next_u64(),
+                    opcode,
+                    rs1: Some(rs1),
+                    rs2: Some(rs2),
+                    rd: Some(rd),
+                    imm: None,
+                    virtual_sequence_remaining: None,
+                    is_compressed: false,
+                },
+                register_state: RegisterState {
+                    rs1_val: Some(address),
+                    rs2_val: Some(source),
+                    rd_post_val: Some(if rd == 0 { 0 } else { rd_value }),
+                },
+                memory_state: Some(memory_state),
+                advice_value,
+                precompile_input: None,
+            };
+
+            let virtual_sequence = I::virtual_trace(trace_row);
+            assert_eq!(virtual_sequence.len(), I::SEQUENCE_LENGTH);
+            for row in virtual_sequence {
+                if let Some(rs1_val) = row.register_state.rs1_val {
+                    assert_eq!(registers[row.instruction.rs1.unwrap() as usize], rs1_val);
+                }
+                if let Some(rs2_val) = row.register_state.rs2_val {
+                    assert_eq!(registers[row.instruction.rs2.unwrap() as usize], rs2_val);
+                }
+
+                let output = RV32I::try_from(&row).unwrap().lookup_entry();
+                match row.memory_state {
+                    Some(MemoryState::Read { address, value }) => {
+                        assert_eq!(memory[&address], value);
+                        assert_eq!(output, value);
+                    }
+                    Some(MemoryState::Write {
+                        address,
+                        post_value,
+                    }) => {
+                        assert_eq!(output, post_value);
+                        memory.insert(address, post_value);
+                    }
+                    None => {}
+                }
+                if let Some(rd) = row.instruction.rd {
+                    assert_eq!(output, row.register_state.rd_post_val.unwrap());
+                    if rd != 0 {
+                        registers[rd as usize] = output;
+                    }
+                }
+            }
+
+            assert_eq!(memory[&address], stored);
+            assert_eq!(registers[RESERVATION_REGISTER as usize], reservation_after);
+            for (index, val) in registers.iter().enumerate().take(32) {
+                if index as u64 == rd && rd != 0 {
+                    assert_eq!(*val, rd_value);
+                } else if index as u64 == rs1 {
+                    assert_eq!(*val, address);
+                } else if index as u64 == rs2 {
+                    assert_eq!(*val, source);
+                } else {
+                    assert_eq!(*val, 0, "Other 'real' registers should not be touched");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lr_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<LRWInstruction<32>>(RV32IM::LR_W);
+    }
+
+    #[test]
+    fn sc_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<SCWInstruction<32>>(RV32IM::SC_W);
+    }
+
+    #[test]
+    fn amoswap_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOSWAPWInstruction<32>>(RV32IM::AMOSWAP_W);
+    }
+
+    #[test]
+    fn amoadd_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOADDWInstruction<32>>(RV32IM::AMOADD_W);
+    }
+
+    #[test]
+    fn amoxor_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOXORWInstruction<32>>(RV32IM::AMOXOR_W);
+    }
+
+    #[test]
+    fn amoand_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOANDWInstruction<32>>(RV32IM::AMOAND_W);
+    }
+
+    #[test]
+    fn amoor_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOORWInstruction<32>>(RV32IM::AMOOR_W);
+    }
+
+    #[test]
+    fn amomin_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOMINWInstruction<32>>(RV32IM::AMOMIN_W);
+    }
+
+    #[test]
+    fn amomax_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOMAXWInstruction<32>>(RV32IM::AMOMAX_W);
+    }
+
+    #[test]
+    fn amominu_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOMINUWInstruction<32>>(RV32IM::AMOMINU_W);
+    }
+
+    #[test]
+    fn amomaxu_w_virtual_sequence_32() {
+        atomic_virtual_sequence_test::<AMOMAXUWInstruction<32>>(RV32IM::AMOMAXU_W);
+    }
+}
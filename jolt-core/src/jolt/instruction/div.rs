@@ -69,6 +69,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: v_q,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: None,
@@ -77,6 +78,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
             },
             memory_state: None,
             advice_value: Some(quotient), // What should advice value be here?
+            precompile_input: None,
         });
 
         let r = ADVICEInstruction::<WORD_SIZE>(remainder).lookup_entry();
@@ -89,6 +91,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: v_r,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: None,
@@ -97,6 +100,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
             },
             memory_state: None,
             advice_value: Some(remainder), // What should advice value be here?
+            precompile_input: None,
         });
 
         let is_valid: u64 = AssertValidSignedRemainderInstruction::<WORD_SIZE>(r, y).lookup_entry();
@@ -110,6 +114,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: Some(r),
@@ -118,6 +123,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
             },
             memory_state: None,
             advice_value: None,
+            precompile_input: None,
         });
 
         let is_valid: u64 = AssertValidDiv0Instruction::<WORD_SIZE>(y, q).lookup_entry();
@@ -131,6 +137,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: Some(y),
@@ -139,6 +146,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
             },
             memory_state: None,
             advice_value: None,
+            precompile_input: None,
         });
 
         let q_y = MULInstruction::<WORD_SIZE>(q, y).lookup_entry();
@@ -151,6 +159,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: v_qy,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: Some(q),
@@ -159,6 +168,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
             },
             memory_state: None,
             advice_value: None,
+            precompile_input: None,
         });
 
         let add_0 = ADDInstruction::<WORD_SIZE>(q_y, r).lookup_entry();
@@ -171,6 +181,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: v_0,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: Some(q_y),
@@ -179,6 +190,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
             },
             memory_state: None,
             advice_value: None,
+            precompile_input: None,
         });
 
         let _assert_eq = BEQInstruction::<WORD_SIZE>(add_0, x).lookup_entry();
@@ -191,6 +203,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: Some(add_0),
@@ -199,6 +212,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
             },
             memory_state: None,
             advice_value: None,
+            precompile_input: None,
         });
 
         virtual_trace.push(RVTraceRow {
@@ -210,6 +224,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: trace_row.instruction.rd,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: Some(q),
@@ -218,6 +233,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
             },
             memory_state: None,
             advice_value: None,
+            precompile_input: None,
         });
 
         virtual_trace
@@ -244,4 +260,38 @@ mod test {
     fn div_virtual_sequence_32() {
         jolt_virtual_sequence_test!(DIVInstruction::<32>, RV32IM::DIV);
     }
+
+    #[test]
+    fn div_compressed_virtual_sequence() {
+        let instruction = ELFInstruction {
+            address: 0x8000_0000,
+            opcode: RV32IM::DIV,
+            rs1: Some(10),
+            rs2: Some(11),
+            rd: Some(12),
+            imm: None,
+            virtual_sequence_remaining: None,
+            is_compressed: true,
+        };
+        let trace_row = RVTraceRow {
+            instruction: instruction.clone(),
+            register_state: RegisterState {
+                rs1_val: Some(7),
+                rs2_val: Some(2),
+                rd_post_val: Some(3),
+            },
+            memory_state: None,
+            advice_value: None,
+            precompile_input: None,
+        };
+
+        // Only the last row advances the PC, by the length of the compressed instruction
+        let bytecode = DIVInstruction::<32>::virtual_sequence(instruction);
+        let virtual_trace = DIVInstruction::<32>::expand_trace(trace_row);
+        assert_eq!(bytecode.len(), virtual_trace.len());
+        for (i, (instruction, row)) in bytecode.iter().zip(virtual_trace.iter()).enumerate() {
+            assert_eq!(instruction, &row.instruction);
+            assert_eq!(instruction.is_compressed, i == bytecode.len() - 1);
+        }
+    }
 }
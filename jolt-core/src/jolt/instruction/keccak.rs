@@ -0,0 +1,375 @@
+//! The virtual sequence of the Keccak-f[1600] precompile, which permutes the 25 little-endian
+//! 64-bit lanes at rs1 in place.
+//!
+//! There are too few virtual registers to hold the state, so it stays in memory and each
+//! round computes its output a plane at a time: it loads the five lanes that π moves to the
+//! plane, applies θ and ρ to them, and stores the plane computed by χ and ι in the slots
+//! they were loaded from. Lanes thus move between slots from round to round, and are back
+//! in place after the last one, as π has order 24. The column parities of θ are
+//! accumulated as each round stores its output, so only the first round loads the whole
+//! state to compute them.
+
+use common::constants::{virtual_register_index, VIRTUAL_REGISTER_COUNT};
+use tracer::{RVTraceRow, KECCAK_ROTATIONS, KECCAK_ROUND_CONSTANTS, RV32IM};
+
+use super::sequence_builder::{Operand, SequenceBuilder};
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{
+    and::ANDInstruction, or::ORInstruction, sll::SLLInstruction, srl::SRLInstruction,
+    virtual_rotr::ROTRInstruction, xor::XORInstruction, JoltInstruction,
+};
+
+/// A lane as its low and high words.
+type Lane = [Operand; 2];
+
+pub struct KeccakFInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for KeccakFInstruction<WORD_SIZE> {
+    // 90 instructions computing the column parities of the input, and 24 rounds. Each round
+    // has 40 instructions combining the parities, and for each plane 10 loads, 10 applying
+    // θ, 30 for χ and 10 stores, plus 6 for each of the 24 rotations of ρ and 2 for ι. All
+    // planes but the first also have 10 accumulating the parities, except in the last round.
+    const SEQUENCE_LENGTH: usize =
+        90 + 24 * (40 + 5 * (10 + 10 + 30 + 10) + 24 * 6 + 2) + 23 * 4 * 10;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::KECCAK_F);
+        assert_eq!(WORD_SIZE, 32);
+        // Zero when expanding bytecode, which has no trace values.
+        let input = trace_row
+            .precompile_input
+            .clone()
+            .unwrap_or_else(|| vec![0; 50]);
+        assert_eq!(input.len(), 50);
+
+        let mut sequence = KeccakSequence {
+            builder: SequenceBuilder::new(&trace_row, Self::SEQUENCE_LENGTH),
+            state_base: (
+                trace_row.instruction.rs1,
+                trace_row.register_state.rs1_val.unwrap(),
+            ),
+            memory: input,
+            slots: std::array::from_fn(|i| i),
+            // All but the reservation register, which holds the reservation of LR.W
+            free_registers: (0..VIRTUAL_REGISTER_COUNT - 1)
+                .rev()
+                .map(virtual_register_index)
+                .collect(),
+        };
+
+        let mut parities = Some(sequence.column_parities());
+        for (round, round_constant) in KECCAK_ROUND_CONSTANTS.into_iter().enumerate() {
+            let d = sequence.theta(parities.unwrap());
+            let last_round = round == KECCAK_ROUND_CONSTANTS.len() - 1;
+            parities = sequence.round(d, round_constant, !last_round);
+        }
+        assert!(sequence.slots.iter().enumerate().all(|(i, slot)| i == *slot));
+        sequence.builder.finish()
+    }
+
+    /// KECCAK_F writes no register.
+    fn sequence_output(_: u64, _: u64) -> u64 {
+        0
+    }
+}
+
+/// Emits the instructions of the permutation, computing their results as they go.
+struct KeccakSequence {
+    builder: SequenceBuilder,
+    state_base: Operand,
+    /// The words of the state in memory, as the sequence has left them.
+    memory: Vec<u64>,
+    /// The slot of the state in memory holding each lane.
+    slots: [usize; 25],
+    /// The virtual registers holding no live value, the next to be allocated last.
+    free_registers: Vec<u64>,
+}
+
+impl KeccakSequence {
+    fn allocate(&mut self) -> Option<u64> {
+        Some(self.free_registers.pop().expect("out of virtual registers"))
+    }
+
+    fn release(&mut self, words: &[Operand]) {
+        self.free_registers
+            .extend(words.iter().map(|word| word.0.unwrap()));
+    }
+
+    fn load(&mut self, lane: usize, half: usize, rd: Option<u64>) -> Operand {
+        let index = 2 * self.slots[lane] + half;
+        let value = self.memory[index];
+        self.builder
+            .load(self.state_base, 4 * index as u32, rd, value)
+    }
+
+    fn store(&mut self, lane: usize, half: usize, word: Operand) {
+        let index = 2 * self.slots[lane] + half;
+        self.memory[index] = word.1;
+        self.builder.store(self.state_base, 4 * index as u32, word);
+    }
+
+    fn or(&mut self, x: Operand, y: Operand, rd: Option<u64>) -> Operand {
+        let z = ORInstruction::<32>(x.1, y.1).lookup_entry();
+        self.builder.alu(RV32IM::OR, x, y, rd, z)
+    }
+
+    fn xor(&mut self, x: Operand, y: Operand, rd: Option<u64>) -> Operand {
+        let z = XORInstruction::<32>(x.1, y.1).lookup_entry();
+        self.builder.alu(RV32IM::XOR, x, y, rd, z)
+    }
+
+    fn andi(&mut self, x: Operand, imm: u32, rd: Option<u64>) -> Operand {
+        let z = ANDInstruction::<32>(x.1, imm as u64).lookup_entry();
+        self.builder.alu_imm(RV32IM::ANDI, x, imm, rd, z)
+    }
+
+    fn xori(&mut self, x: Operand, imm: u32, rd: Option<u64>) -> Operand {
+        let z = XORInstruction::<32>(x.1, imm as u64).lookup_entry();
+        self.builder.alu_imm(RV32IM::XORI, x, imm, rd, z)
+    }
+
+    fn slli(&mut self, x: Operand, shift: u32, rd: Option<u64>) -> Operand {
+        let z = SLLInstruction::<32>(x.1, shift as u64).lookup_entry();
+        self.builder.alu_imm(RV32IM::SLLI, x, shift, rd, z)
+    }
+
+    fn srli(&mut self, x: Operand, shift: u32, rd: Option<u64>) -> Operand {
+        let z = SRLInstruction::<32>(x.1, shift as u64).lookup_entry();
+        self.builder.alu_imm(RV32IM::SRLI, x, shift, rd, z)
+    }
+
+    fn rotri(&mut self, x: Operand, shift: u32, rd: Option<u64>) -> Operand {
+        let z = ROTRInstruction::<32>(x.1, shift as u64).lookup_entry();
+        self.builder.alu_imm(RV32IM::VIRTUAL_ROTRI, x, shift, rd, z)
+    }
+
+    /// Rotates `lane` left by `shift`, which is not a multiple of 32, in place.
+    fn rotate_left(&mut self, lane: Lane, shift: u32) -> Lane {
+        assert_ne!(shift % 32, 0);
+        let [low, high] = if shift < 32 { lane } else { [lane[1], lane[0]] };
+        let shift = shift % 32;
+
+        // Rotating each word leaves the bits that belong to the other word in its low
+        // `shift` bits, which are then swapped.
+        let low = self.rotri(low, 32 - shift, low.0);
+        let high = self.rotri(high, 32 - shift, high.0);
+        let temp = self.allocate();
+        let swapped = self.xor(low, high, temp);
+        let swapped = self.andi(swapped, (1 << shift) - 1, temp);
+        let low = self.xor(low, swapped, low.0);
+        let high = self.xor(high, swapped, high.0);
+        self.release(&[swapped]);
+        [low, high]
+    }
+
+    /// Loads the state to compute its column parities.
+    fn column_parities(&mut self) -> [Lane; 5] {
+        std::array::from_fn(|x| {
+            std::array::from_fn(|half| {
+                let rd = self.allocate();
+                let mut parity = self.load(x, half, rd);
+                for y in 1..5 {
+                    let temp = self.allocate();
+                    let word = self.load(x + 5 * y, half, temp);
+                    parity = self.xor(parity, word, rd);
+                    self.release(&[word]);
+                }
+                parity
+            })
+        })
+    }
+
+    /// Combines the column parities `c` into the words θ adds to each column.
+    fn theta(&mut self, c: [Lane; 5]) -> [Lane; 5] {
+        let d = std::array::from_fn(|x| {
+            // c[x - 1] ^ rotate_left(c[x + 1], 1), rotating into new registers as c[x + 1]
+            // is also needed for d[x + 2]
+            let [low, high] = c[(x + 1) % 5];
+            let temp = self.allocate();
+            let new_low = self.allocate();
+            let new_high = self.allocate();
+            let shifted = self.slli(low, 1, new_low);
+            let carry = self.srli(high, 31, temp);
+            let rotated_low = self.or(shifted, carry, new_low);
+            let shifted = self.slli(high, 1, new_high);
+            let carry = self.srli(low, 31, temp);
+            let rotated_high = self.or(shifted, carry, new_high);
+            self.release(&[carry]);
+            [
+                self.xor(c[(x + 4) % 5][0], rotated_low, new_low),
+                self.xor(c[(x + 4) % 5][1], rotated_high, new_high),
+            ]
+        });
+        self.release(c.as_flattened());
+        d
+    }
+
+    /// The rest of a round, given the words `d` that θ adds to each column. Returns the
+    /// column parities of its output if `next_parities` is set.
+    fn round(
+        &mut self,
+        d: [Lane; 5],
+        round_constant: u64,
+        next_parities: bool,
+    ) -> Option<[Lane; 5]> {
+        let mut parities = [[(None, 0); 2]; 5];
+        let mut slots = [0; 25];
+        for y in 0..5 {
+            // π moves lane (x + 3y, x) to (x, y).
+            let sources: [usize; 5] = std::array::from_fn(|x| (x + 3 * y) % 5 + 5 * x);
+            let b: [Lane; 5] = std::array::from_fn(|x| {
+                let source = sources[x];
+                let lane = std::array::from_fn(|half| {
+                    let rd = self.allocate();
+                    let word = self.load(source, half, rd);
+                    self.xor(word, d[source % 5][half], rd)
+                });
+                match KECCAK_ROTATIONS[source] {
+                    0 => lane,
+                    shift => self.rotate_left(lane, shift),
+                }
+            });
+
+            for half in 0..2 {
+                let b: [Operand; 5] = std::array::from_fn(|x| b[x][half]);
+                for x in 0..5 {
+                    // b[x] ^ (!b[x + 1] & b[x + 2]) = b[x] ^ ((b[x + 1] | b[x + 2]) ^ b[x + 1])
+                    let temp = self.allocate();
+                    let word = self.or(b[(x + 1) % 5], b[(x + 2) % 5], temp);
+                    let word = self.xor(word, b[(x + 1) % 5], temp);
+                    let rd = if y == 0 && next_parities {
+                        self.allocate()
+                    } else {
+                        temp
+                    };
+                    let mut word = self.xor(b[x], word, rd);
+                    if x == 0 && y == 0 {
+                        // ι
+                        word = self.xori(word, (round_constant >> (32 * half)) as u32, rd);
+                    }
+                    self.store(sources[x], half, word);
+                    if next_parities {
+                        parities[x][half] = match y {
+                            0 => word,
+                            _ => self.xor(parities[x][half], word, parities[x][half].0),
+                        };
+                    }
+                    self.free_registers.push(temp.unwrap());
+                }
+                self.release(&b);
+            }
+
+            for (x, source) in sources.into_iter().enumerate() {
+                slots[x + 5 * y] = self.slots[source];
+            }
+        }
+        self.release(d.as_flattened());
+        self.slots = slots;
+        next_parities.then_some(parities)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use ark_std::test_rng;
+    use common::constants::REGISTER_COUNT;
+    use rand_chacha::rand_core::RngCore;
+    use tracer::{keccak_f, ELFInstruction, MemoryState, RegisterState};
+
+    use super::*;
+
+    /// Executes the virtual sequence of a KECCAK_F traced on a random register and state,
+    /// and checks that memory ends up holding the permuted state, that the registers the
+    /// sequence reads hold the values it expects, and that its instructions match the
+    /// expanded bytecode.
+    #[test]
+    fn keccak_f_virtual_sequence() {
+        let mut rng = test_rng();
+        for _ in 0..4 {
+            let rs1 = 10;
+            let state_address = 0x8000_0000 + 8 * (rng.next_u32() as u64 % 1024);
+
+            let mut state: [u64; 25] = std::array::from_fn(|_| rng.next_u64());
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            registers[rs1 as usize] = state_address;
+            let mut memory: HashMap<u64, u64> = HashMap::new();
+            let words: Vec<u64> = state
+                .iter()
+                .flat_map(|lane| [*lane as u32 as u64, *lane >> 32])
+                .collect();
+            for (i, word) in words.iter().enumerate() {
+                memory.insert(state_address + 4 * i as u64, *word);
+            }
+
+            let instruction = ELFInstruction {
+                address: rng.next_u64(),
+                opcode: RV32IM::KECCAK_F,
+                rs1: Some(rs1),
+                rs2: Some(0),
+                rd: Some(0),
+                imm: None,
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            };
+            let trace_row = RVTraceRow {
+                instruction: instruction.clone(),
+                register_state: RegisterState {
+                    rs1_val: Some(state_address),
+                    rs2_val: Some(0),
+                    rd_post_val: Some(0),
+                },
+                memory_state: None,
+                advice_value: None,
+                precompile_input: Some(words),
+            };
+
+            let virtual_sequence = KeccakFInstruction::<32>::virtual_trace(trace_row);
+            assert_eq!(
+                virtual_sequence.len(),
+                KeccakFInstruction::<32>::SEQUENCE_LENGTH
+            );
+            let bytecode = KeccakFInstruction::<32>::virtual_sequence(instruction);
+            for (row, expected_instruction) in virtual_sequence.iter().zip(bytecode.iter()) {
+                assert_eq!(&row.instruction, expected_instruction);
+            }
+
+            for row in virtual_sequence {
+                if let Some(rs1_val) = row.register_state.rs1_val {
+                    assert_eq!(registers[row.instruction.rs1.unwrap() as usize], rs1_val);
+                }
+                if let Some(rs2_val) = row.register_state.rs2_val {
+                    assert_eq!(registers[row.instruction.rs2.unwrap() as usize], rs2_val);
+                }
+                match row.memory_state {
+                    Some(MemoryState::Read { address, value }) => {
+                        assert_eq!(memory[&address], value);
+                    }
+                    Some(MemoryState::Write {
+                        address,
+                        post_value,
+                    }) => {
+                        memory.insert(address, post_value);
+                    }
+                    None => {}
+                }
+                if let (Some(rd), Some(rd_post_val)) =
+                    (row.instruction.rd, row.register_state.rd_post_val)
+                {
+                    if rd != 0 {
+                        registers[rd as usize] = rd_post_val;
+                    }
+                }
+            }
+
+            keccak_f(&mut state);
+            for (i, lane) in state.iter().enumerate() {
+                let address = state_address + 8 * i as u64;
+                assert_eq!(memory[&address], *lane as u32 as u64);
+                assert_eq!(memory[&(address + 4)], *lane >> 32);
+            }
+            assert_eq!(registers[rs1 as usize], state_address);
+        }
+    }
+}
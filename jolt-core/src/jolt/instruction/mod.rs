@@ -5,14 +5,15 @@ use serde::Serialize;
 use std::marker::Sync;
 use std::ops::Range;
 use strum::{EnumCount, IntoEnumIterator};
-use tracer::{RVTraceRow, RegisterState};
+use tracer::RegisterState;
 
 use crate::field::JoltField;
 use crate::jolt::subtable::LassoSubtable;
 use crate::utils::instruction_utils::chunk_operand;
-use common::rv_trace::ELFInstruction;
 use std::fmt::Debug;
 
+pub use tracer::{CustomInstruction, ELFInstruction, RVTraceRow, RV32IM};
+
 #[enum_dispatch]
 pub trait JoltInstruction: Clone + Debug + Send + Sync + Serialize {
     fn operands(&self) -> (u64, u64);
@@ -78,6 +79,44 @@ pub trait JoltInstructionSet:
         let byte = unsafe { *(instruction as *const Self as *const u8) };
         byte as usize
     }
+
+    /// The custom instructions in this instruction set, bound to their encodings when the
+    /// guest is traced.
+    fn custom_instructions() -> Vec<CustomInstruction> {
+        vec![]
+    }
+}
+
+/// An instruction bound to an encoding of the custom-0 or custom-1 RISC-V opcode, which
+/// guests can emit with e.g. `.insn r 0x0b, funct3, funct7, rd, rs1, rs2`. It is an R-type
+/// instruction whose lookup output is written to rd. As for the bitwise instructions, its
+/// operands are the values of rs1 and rs2, and its lookup indices are their concatenated
+/// chunks (see `chunk_and_concatenate_operands`). See
+/// [`custom_instruction_set!`](crate::custom_instruction_set) for adding it to a VM.
+pub trait JoltCustomInstruction: JoltInstruction + Default {
+    /// `RV32IM::CUSTOM_0` or `RV32IM::CUSTOM_1`
+    const OPCODE: RV32IM;
+    const FUNCT3: u32;
+    const FUNCT7: u32;
+
+    /// The instruction applied to the values of rs1 and rs2.
+    fn from_operands(x: u64, y: u64) -> Self;
+
+    /// Whether `instruction` is this custom instruction.
+    fn matches(instruction: &ELFInstruction) -> bool {
+        instruction.opcode == Self::OPCODE
+            && instruction.custom_funct() == Some((Self::FUNCT3, Self::FUNCT7))
+    }
+
+    /// The instruction as executed by the tracer, which writes its lookup entry to rd.
+    fn tracer_instruction() -> CustomInstruction {
+        CustomInstruction {
+            opcode: Self::OPCODE,
+            funct3: Self::FUNCT3,
+            funct7: Self::FUNCT7,
+            execute: |x, y| Self::from_operands(x, y).lookup_entry(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -137,24 +176,39 @@ pub trait VirtualInstructionSequence {
             },
             memory_state: None,
             advice_value: None,
+            precompile_input: None,
         };
-        Self::virtual_trace(dummy_trace_row)
+        Self::expand_trace(dummy_trace_row)
             .into_iter()
             .map(|trace_row| trace_row.instruction)
             .collect()
     }
+    /// `virtual_trace`, with the instruction's `is_compressed` flag only kept on the last
+    /// row of the sequence, as all rows share the instruction's address and only the last
+    /// one advances the PC.
+    fn expand_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        let is_compressed = trace_row.instruction.is_compressed;
+        let mut virtual_trace = Self::virtual_trace(trace_row);
+        for row in virtual_trace.iter_mut() {
+            row.instruction.is_compressed =
+                is_compressed && row.instruction.virtual_sequence_remaining == Some(0);
+        }
+        virtual_trace
+    }
     fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow>;
     fn sequence_output(x: u64, y: u64) -> u64;
 }
 
 pub mod add;
 pub mod and;
+pub mod atomic;
 pub mod beq;
 pub mod bge;
 pub mod bgeu;
 pub mod bne;
 pub mod div;
 pub mod divu;
+pub mod keccak;
 pub mod lb;
 pub mod lh;
 pub mod mul;
@@ -166,7 +220,9 @@ pub mod or;
 pub mod rem;
 pub mod remu;
 pub mod sb;
+mod sequence_builder;
 pub mod sh;
+pub mod sha256;
 pub mod sll;
 pub mod slt;
 pub mod sltu;
@@ -181,6 +237,7 @@ pub mod virtual_assert_valid_signed_remainder;
 pub mod virtual_assert_valid_unsigned_remainder;
 pub mod virtual_move;
 pub mod virtual_movsign;
+pub mod virtual_rotr;
 pub mod xor;
 
 #[cfg(test)]
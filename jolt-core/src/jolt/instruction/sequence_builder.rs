@@ -0,0 +1,145 @@
+use tracer::{ELFInstruction, MemoryState, RVTraceRow, RegisterState, RV32IM};
+
+/// A register and its value at a step of a virtual sequence.
+pub(super) type Operand = (Option<u64>, u64);
+
+/// Builds the rows of a virtual sequence, all sharing the address of the instruction it
+/// expands.
+pub(super) struct SequenceBuilder {
+    address: u64,
+    sequence_length: usize,
+    rows: Vec<RVTraceRow>,
+}
+
+impl SequenceBuilder {
+    pub(super) fn new(trace_row: &RVTraceRow, sequence_length: usize) -> Self {
+        Self {
+            address: trace_row.instruction.address,
+            sequence_length,
+            rows: Vec::with_capacity(sequence_length),
+        }
+    }
+
+    fn push(
+        &mut self,
+        instruction: (RV32IM, Option<u64>, Option<u64>, Option<u64>, Option<u32>),
+        register_state: RegisterState,
+        memory_state: Option<MemoryState>,
+    ) {
+        let (opcode, rs1, rs2, rd, imm) = instruction;
+        self.rows.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: self.address,
+                opcode,
+                rs1,
+                rs2,
+                rd,
+                imm,
+                virtual_sequence_remaining: Some(self.sequence_length - self.rows.len() - 1),
+                is_compressed: false,
+            },
+            register_state,
+            memory_state,
+            advice_value: None,
+            precompile_input: None,
+        });
+    }
+
+    /// `LW rd, offset(rs1)`
+    pub(super) fn load(
+        &mut self,
+        base: Operand,
+        offset: u32,
+        rd: Option<u64>,
+        value: u64,
+    ) -> Operand {
+        self.push(
+            (RV32IM::LW, base.0, None, rd, Some(offset)),
+            RegisterState {
+                rs1_val: Some(base.1),
+                rs2_val: None,
+                rd_post_val: Some(value),
+            },
+            Some(MemoryState::Read {
+                address: base.1 + offset as u64,
+                value,
+            }),
+        );
+        (rd, value)
+    }
+
+    /// `SW rs2, offset(rs1)`
+    pub(super) fn store(&mut self, base: Operand, offset: u32, source: Operand) {
+        self.push(
+            (RV32IM::SW, base.0, source.0, None, Some(offset)),
+            RegisterState {
+                rs1_val: Some(base.1),
+                rs2_val: Some(source.1),
+                rd_post_val: None,
+            },
+            Some(MemoryState::Write {
+                address: base.1 + offset as u64,
+                post_value: source.1,
+            }),
+        );
+    }
+
+    /// An R-type ALU instruction computing `z` from `x` and `y`.
+    pub(super) fn alu(
+        &mut self,
+        opcode: RV32IM,
+        x: Operand,
+        y: Operand,
+        rd: Option<u64>,
+        z: u64,
+    ) -> Operand {
+        self.push(
+            (opcode, x.0, y.0, rd, None),
+            RegisterState {
+                rs1_val: Some(x.1),
+                rs2_val: Some(y.1),
+                rd_post_val: Some(z),
+            },
+            None,
+        );
+        (rd, z)
+    }
+
+    /// An I-type ALU instruction computing `z` from `x` and `imm`.
+    pub(super) fn alu_imm(
+        &mut self,
+        opcode: RV32IM,
+        x: Operand,
+        imm: u32,
+        rd: Option<u64>,
+        z: u64,
+    ) -> Operand {
+        self.push(
+            (opcode, x.0, None, rd, Some(imm)),
+            RegisterState {
+                rs1_val: Some(x.1),
+                rs2_val: None,
+                rd_post_val: Some(z),
+            },
+            None,
+        );
+        (rd, z)
+    }
+
+    pub(super) fn mov(&mut self, source: Operand, rd: Option<u64>) {
+        self.push(
+            (RV32IM::VIRTUAL_MOVE, source.0, None, rd, None),
+            RegisterState {
+                rs1_val: Some(source.1),
+                rs2_val: None,
+                rd_post_val: Some(source.1),
+            },
+            None,
+        );
+    }
+
+    pub(super) fn finish(self) -> Vec<RVTraceRow> {
+        assert_eq!(self.rows.len(), self.sequence_length);
+        self.rows
+    }
+}
@@ -0,0 +1,308 @@
+//! The virtual sequence of the SHA-256 compression precompile, which compresses the state
+//! of eight words at rs1 with the block of sixteen big-endian message words at rs2.
+//!
+//! The working variables and a sliding window of the message schedule are kept in virtual
+//! registers, so memory is only accessed to load the state and block and to store the new
+//! state. The rotations of the Σ and σ functions are single `VIRTUAL_ROTRI` lookups.
+
+use common::constants::virtual_register_index;
+use tracer::{RVTraceRow, RV32IM, SHA256_K};
+
+use super::sequence_builder::{Operand, SequenceBuilder};
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{
+    add::ADDInstruction, and::ANDInstruction, or::ORInstruction, srl::SRLInstruction,
+    virtual_rotr::ROTRInstruction, xor::XORInstruction, JoltInstruction,
+};
+
+/// The working variables a..h are held in the virtual registers 0..8, in an order that
+/// changes from round to round.
+const WORKING_REGISTERS: u64 = 0;
+/// `W[t]` is held in the virtual register `SCHEDULE_REGISTERS + t % 16`.
+const SCHEDULE_REGISTERS: u64 = 8;
+/// The intermediate values of a step are held in the virtual registers 24..27.
+const TEMP_REGISTERS: u64 = 24;
+
+pub struct SHA256CompressInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for SHA256CompressInstruction<WORD_SIZE> {
+    // Loads of the state and block, 48 message schedule steps of 13 instructions, 64 rounds
+    // of 24 instructions, and an addition and store for each word of the state.
+    const SEQUENCE_LENGTH: usize = 24 + 48 * 13 + 64 * 24 + 3 * 8;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::SHA256_COMPRESS);
+        assert_eq!(WORD_SIZE, 32);
+        let state_base = (
+            trace_row.instruction.rs1,
+            trace_row.register_state.rs1_val.unwrap(),
+        );
+        let block_base = (
+            trace_row.instruction.rs2,
+            trace_row.register_state.rs2_val.unwrap(),
+        );
+        // Zero when expanding bytecode, which has no trace values.
+        let input = trace_row
+            .precompile_input
+            .clone()
+            .unwrap_or_else(|| vec![0; 24]);
+        assert_eq!(input.len(), 24);
+
+        let mut sequence = SHA256Sequence {
+            builder: SequenceBuilder::new(&trace_row, Self::SEQUENCE_LENGTH),
+        };
+
+        let mut vars: [Operand; 8] = std::array::from_fn(|i| {
+            let register = Some(virtual_register_index(WORKING_REGISTERS + i as u64));
+            sequence
+                .builder
+                .load(state_base, 4 * i as u32, register, input[i])
+        });
+        let mut w: [Operand; 16] = std::array::from_fn(|i| {
+            let register = Some(virtual_register_index(SCHEDULE_REGISTERS + i as u64));
+            sequence
+                .builder
+                .load(block_base, 4 * i as u32, register, input[8 + i])
+        });
+
+        for t in 0..64 {
+            if t >= 16 {
+                w[t % 16] = sequence.schedule(&w, t);
+            }
+            vars = sequence.round(vars, w[t % 16], SHA256_K[t]);
+        }
+
+        let temp = Some(virtual_register_index(TEMP_REGISTERS));
+        for (i, var) in vars.into_iter().enumerate() {
+            let word = sequence
+                .builder
+                .load(state_base, 4 * i as u32, temp, input[i]);
+            let word = sequence.add(word, var, temp);
+            sequence.builder.store(state_base, 4 * i as u32, word);
+        }
+        sequence.builder.finish()
+    }
+
+    /// SHA256_COMPRESS writes no register.
+    fn sequence_output(_: u64, _: u64) -> u64 {
+        0
+    }
+}
+
+/// Emits the instructions of the compression function, computing their results as they go.
+struct SHA256Sequence {
+    builder: SequenceBuilder,
+}
+
+impl SHA256Sequence {
+    fn add(&mut self, x: Operand, y: Operand, rd: Option<u64>) -> Operand {
+        let z = ADDInstruction::<32>(x.1, y.1).lookup_entry();
+        self.builder.alu(RV32IM::ADD, x, y, rd, z)
+    }
+
+    fn addi(&mut self, x: Operand, imm: u32, rd: Option<u64>) -> Operand {
+        let z = ADDInstruction::<32>(x.1, imm as u64).lookup_entry();
+        self.builder.alu_imm(RV32IM::ADDI, x, imm, rd, z)
+    }
+
+    fn and(&mut self, x: Operand, y: Operand, rd: Option<u64>) -> Operand {
+        let z = ANDInstruction::<32>(x.1, y.1).lookup_entry();
+        self.builder.alu(RV32IM::AND, x, y, rd, z)
+    }
+
+    fn or(&mut self, x: Operand, y: Operand, rd: Option<u64>) -> Operand {
+        let z = ORInstruction::<32>(x.1, y.1).lookup_entry();
+        self.builder.alu(RV32IM::OR, x, y, rd, z)
+    }
+
+    fn xor(&mut self, x: Operand, y: Operand, rd: Option<u64>) -> Operand {
+        let z = XORInstruction::<32>(x.1, y.1).lookup_entry();
+        self.builder.alu(RV32IM::XOR, x, y, rd, z)
+    }
+
+    fn rotri(&mut self, x: Operand, shift: u32, rd: Option<u64>) -> Operand {
+        let z = ROTRInstruction::<32>(x.1, shift as u64).lookup_entry();
+        self.builder.alu_imm(RV32IM::VIRTUAL_ROTRI, x, shift, rd, z)
+    }
+
+    fn srli(&mut self, x: Operand, shift: u32, rd: Option<u64>) -> Operand {
+        let z = SRLInstruction::<32>(x.1, shift as u64).lookup_entry();
+        self.builder.alu_imm(RV32IM::SRLI, x, shift, rd, z)
+    }
+
+    /// `rotr(x, r0) ^ rotr(x, r1) ^ f(x, r2)`, where `f` is rotr if `rotate_last` and shr
+    /// otherwise. Computed in the first temp register, clobbering the second.
+    fn sigma(&mut self, x: Operand, shifts: [u32; 3], rotate_last: bool) -> Operand {
+        let t0 = Some(virtual_register_index(TEMP_REGISTERS));
+        let t1 = Some(virtual_register_index(TEMP_REGISTERS + 1));
+        let r0 = self.rotri(x, shifts[0], t0);
+        let r1 = self.rotri(x, shifts[1], t1);
+        let r = self.xor(r0, r1, t0);
+        let r2 = if rotate_last {
+            self.rotri(x, shifts[2], t1)
+        } else {
+            self.srli(x, shifts[2], t1)
+        };
+        self.xor(r, r2, t0)
+    }
+
+    /// Computes `W[t]` in place of `W[t - 16]`.
+    fn schedule(&mut self, w: &[Operand; 16], t: usize) -> Operand {
+        let t1 = Some(virtual_register_index(TEMP_REGISTERS + 1));
+        let t2 = Some(virtual_register_index(TEMP_REGISTERS + 2));
+        let s0 = self.sigma(w[(t - 15) % 16], [7, 18, 3], false);
+        // σ1 is computed in the second temp register, keeping σ0 in the first.
+        let r0 = self.rotri(w[(t - 2) % 16], 17, t1);
+        let r1 = self.rotri(w[(t - 2) % 16], 19, t2);
+        let r = self.xor(r0, r1, t1);
+        let r2 = self.srli(w[(t - 2) % 16], 10, t2);
+        let s1 = self.xor(r, r2, t1);
+
+        let rd = w[t % 16].0;
+        let sum = self.add(w[t % 16], s0, rd);
+        let sum = self.add(sum, w[(t - 7) % 16], rd);
+        self.add(sum, s1, rd)
+    }
+
+    /// A round of the compression function on the working variables a..h, returning the
+    /// new working variables. The new a is computed in h's register and the new e in d's.
+    fn round(&mut self, vars: [Operand; 8], w: Operand, k: u32) -> [Operand; 8] {
+        let [a, b, c, d, e, f, g, h] = vars;
+        let t1 = Some(virtual_register_index(TEMP_REGISTERS + 1));
+        let t2 = Some(virtual_register_index(TEMP_REGISTERS + 2));
+
+        let s1 = self.sigma(e, [6, 11, 25], true);
+        // ch(e, f, g) = g ^ (e & (f ^ g))
+        let ch = self.xor(f, g, t1);
+        let ch = self.and(e, ch, t1);
+        let ch = self.xor(g, ch, t1);
+        let temp1 = self.add(h, s1, h.0);
+        let temp1 = self.add(temp1, ch, h.0);
+        let temp1 = self.addi(temp1, k, h.0);
+        let temp1 = self.add(temp1, w, h.0);
+        let new_e = self.add(d, temp1, d.0);
+
+        let s0 = self.sigma(a, [2, 13, 22], true);
+        // maj(a, b, c) = (c & (a | b)) | (a & b)
+        let maj = self.or(a, b, t1);
+        let maj = self.and(c, maj, t1);
+        let ab = self.and(a, b, t2);
+        let maj = self.or(maj, ab, t1);
+        let new_a = self.add(temp1, s0, h.0);
+        let new_a = self.add(new_a, maj, h.0);
+
+        [new_a, a, b, c, new_e, e, f, g]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use ark_std::test_rng;
+    use common::constants::REGISTER_COUNT;
+    use rand_chacha::rand_core::RngCore;
+    use tracer::{sha256_compress, ELFInstruction, MemoryState, RegisterState};
+
+    use super::*;
+
+    /// Executes the virtual sequence of a SHA256_COMPRESS traced on random registers, state
+    /// and block, and checks that memory ends up holding the compressed state, that the
+    /// registers the sequence reads hold the values it expects, and that its instructions
+    /// match the expanded bytecode.
+    #[test]
+    fn sha256_compress_virtual_sequence() {
+        let mut rng = test_rng();
+        for _ in 0..4 {
+            let (rs1, rs2) = (10, 11);
+            let state_address = 0x8000_0000 + 4 * (rng.next_u32() as u64 % 1024);
+            let block_address = 0x8001_0000 + 4 * (rng.next_u32() as u64 % 1024);
+
+            let mut state: [u32; 8] = std::array::from_fn(|_| rng.next_u32());
+            let block: [u32; 16] = std::array::from_fn(|_| rng.next_u32());
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            registers[rs1 as usize] = state_address;
+            registers[rs2 as usize] = block_address;
+            let mut memory: HashMap<u64, u64> = HashMap::new();
+            for (i, word) in state.iter().enumerate() {
+                memory.insert(state_address + 4 * i as u64, *word as u64);
+            }
+            for (i, word) in block.iter().enumerate() {
+                memory.insert(block_address + 4 * i as u64, *word as u64);
+            }
+
+            let instruction = ELFInstruction {
+                address: rng.next_u64(),
+                opcode: RV32IM::SHA256_COMPRESS,
+                rs1: Some(rs1),
+                rs2: Some(rs2),
+                rd: Some(0),
+                imm: None,
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            };
+            let trace_row = RVTraceRow {
+                instruction: instruction.clone(),
+                register_state: RegisterState {
+                    rs1_val: Some(state_address),
+                    rs2_val: Some(block_address),
+                    rd_post_val: Some(0),
+                },
+                memory_state: None,
+                advice_value: None,
+                precompile_input: Some(
+                    state
+                        .iter()
+                        .chain(block.iter())
+                        .map(|word| *word as u64)
+                        .collect(),
+                ),
+            };
+
+            let virtual_sequence = SHA256CompressInstruction::<32>::virtual_trace(trace_row);
+            assert_eq!(
+                virtual_sequence.len(),
+                SHA256CompressInstruction::<32>::SEQUENCE_LENGTH
+            );
+            let bytecode = SHA256CompressInstruction::<32>::virtual_sequence(instruction);
+            for (row, expected_instruction) in virtual_sequence.iter().zip(bytecode.iter()) {
+                assert_eq!(&row.instruction, expected_instruction);
+            }
+
+            for row in virtual_sequence {
+                if let Some(rs1_val) = row.register_state.rs1_val {
+                    assert_eq!(registers[row.instruction.rs1.unwrap() as usize], rs1_val);
+                }
+                if let Some(rs2_val) = row.register_state.rs2_val {
+                    assert_eq!(registers[row.instruction.rs2.unwrap() as usize], rs2_val);
+                }
+                match row.memory_state {
+                    Some(MemoryState::Read { address, value }) => {
+                        assert_eq!(memory[&address], value);
+                    }
+                    Some(MemoryState::Write {
+                        address,
+                        post_value,
+                    }) => {
+                        memory.insert(address, post_value);
+                    }
+                    None => {}
+                }
+                if let (Some(rd), Some(rd_post_val)) =
+                    (row.instruction.rd, row.register_state.rd_post_val)
+                {
+                    if rd != 0 {
+                        registers[rd as usize] = rd_post_val;
+                    }
+                }
+            }
+
+            sha256_compress(&mut state, &block);
+            for (i, word) in state.iter().enumerate() {
+                assert_eq!(memory[&(state_address + 4 * i as u64)], *word as u64);
+            }
+            assert_eq!(registers[rs1 as usize], state_address);
+            assert_eq!(registers[rs2 as usize], block_address);
+        }
+    }
+}
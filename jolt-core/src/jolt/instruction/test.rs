@@ -63,6 +63,7 @@ macro_rules! jolt_virtual_sequence_test {
                 rd: Some(rd),
                 imm: None,
                 virtual_sequence_remaining: None,
+                is_compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: Some(x),
@@ -71,6 +72,7 @@ macro_rules! jolt_virtual_sequence_test {
             },
             memory_state: None,
             advice_value: None,
+            precompile_input: None,
         };
 
         let virtual_sequence = <$instr_type>::virtual_trace(trace_row);
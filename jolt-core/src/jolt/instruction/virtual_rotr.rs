@@ -0,0 +1,156 @@
+use crate::field::JoltField;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{JoltInstruction, SubtableIndices};
+use crate::jolt::subtable::{rotr::RotrSubtable, LassoSubtable};
+use crate::utils::instruction_utils::{assert_valid_parameters, chunk_and_concatenate_for_shift};
+
+/// Rotates the first operand right by the second operand (mod `WORD_SIZE`). Used by the
+/// virtual sequences of precompiles as `VIRTUAL_ROTRI`, with an immediate rotation.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct ROTRInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for ROTRInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, self.1)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, _: usize) -> F {
+        assert!(C <= 10);
+        assert!(vals.len() == C);
+        vals.iter().sum()
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        // We have to pre-define subtables in this way because `CHUNK_INDEX` needs to be a constant,
+        // i.e. known at compile time (so we cannot do a `map` over the range of `C`,
+        // which only happens at runtime).
+        let mut subtables: Vec<Box<dyn LassoSubtable<F>>> = vec![
+            Box::new(RotrSubtable::<F, 0, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 1, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 2, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 3, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 4, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 5, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 6, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 7, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 8, WORD_SIZE>::new()),
+            Box::new(RotrSubtable::<F, 9, WORD_SIZE>::new()),
+        ];
+        subtables.truncate(C);
+        subtables.reverse();
+
+        let indices = (0..C).map(SubtableIndices::from);
+        subtables.into_iter().zip(indices).collect()
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        assert_valid_parameters(WORD_SIZE, C, log_M);
+        chunk_and_concatenate_for_shift(self.0, self.1, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        if WORD_SIZE == 32 {
+            let x = self.0 as u32;
+            let y = (self.1 % 32) as u32;
+            x.rotate_right(y).into()
+        } else if WORD_SIZE == 64 {
+            let x = self.0;
+            let y = (self.1 % 64) as u32;
+            x.rotate_right(y)
+        } else {
+            panic!("ROTR is only implemented for 32-bit or 64-bit word sizes")
+        }
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64, rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64(), rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::ROTRInstruction;
+
+    #[test]
+    fn rotr_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32(), rng.next_u32());
+            let instruction = ROTRInstruction::<WORD_SIZE>(x as u64, y as u64);
+            jolt_instruction_test!(instruction);
+        }
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            ROTRInstruction::<32>(100, 0),
+            ROTRInstruction::<32>(0, 100),
+            ROTRInstruction::<32>(1, 0),
+            ROTRInstruction::<32>(0, u32_max),
+            ROTRInstruction::<32>(u32_max, 0),
+            ROTRInstruction::<32>(u32_max, u32_max),
+            ROTRInstruction::<32>(u32_max, 1 << 8),
+            ROTRInstruction::<32>(1 << 8, u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn rotr_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 64;
+
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u64(), rng.next_u64());
+            let instruction = ROTRInstruction::<WORD_SIZE>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+
+        let u64_max: u64 = u64::MAX;
+        let instructions = vec![
+            ROTRInstruction::<64>(100, 0),
+            ROTRInstruction::<64>(0, 2),
+            ROTRInstruction::<64>(1, 2),
+            ROTRInstruction::<64>(0, 64),
+            ROTRInstruction::<64>(u64_max, 0),
+            ROTRInstruction::<64>(u64_max, 63),
+            ROTRInstruction::<64>(u64_max, 1 << 8),
+            ROTRInstruction::<64>(1 << 32, 1 << 16),
+            ROTRInstruction::<64>(1 << 63, 1),
+            ROTRInstruction::<64>((1 << 63) - 1, 1),
+        ];
+
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
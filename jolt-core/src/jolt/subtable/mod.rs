@@ -41,6 +41,7 @@ pub mod ltu;
 pub mod or;
 pub mod right_is_zero;
 pub mod right_msb;
+pub mod rotr;
 pub mod sign_extend;
 pub mod sll;
 pub mod sra_sign;
@@ -0,0 +1,122 @@
+use crate::field::JoltField;
+use ark_std::log2;
+use std::cmp::min;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::math::Math;
+use crate::utils::split_bits;
+
+#[derive(Default)]
+pub struct RotrSubtable<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize>
+    RotrSubtable<F, CHUNK_INDEX, WORD_SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize> LassoSubtable<F>
+    for RotrSubtable<F, CHUNK_INDEX, WORD_SIZE>
+{
+    fn materialize(&self, M: usize) -> Vec<F> {
+        // table[x | y] = rotr(x << suffix_length, y % WORD_SIZE), rotating within WORD_SIZE bits,
+        // where `suffix_length = operand_chunk_width * CHUNK_INDEX`
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+
+        let operand_chunk_width: usize = (log2(M) / 2) as usize;
+        let suffix_length = operand_chunk_width * CHUNK_INDEX;
+        let word_mask = u64::MAX >> (64 - WORD_SIZE);
+
+        for idx in 0..M {
+            let (x, y) = split_bits(idx, operand_chunk_width);
+
+            let shifted = (x as u64).checked_shl(suffix_length as u32).unwrap_or(0) & word_mask;
+            let k = (y % WORD_SIZE) as u32;
+            let row = if k == 0 {
+                shifted
+            } else {
+                ((shifted >> k) | (shifted << (WORD_SIZE as u32 - k))) & word_mask
+            };
+
+            entries.push(F::from_u64(row).unwrap());
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // \sum_{k = 0}^{2^b - 1} eq(y, bin(k)) * (\sum_{j = 0}^{chunk_length - 1} 2^{(b * CHUNK_INDEX + j - k) mod WORD_SIZE} * x_{b - j - 1}),
+        // where chunk_length = min(b, WORD_SIZE - b * CHUNK_INDEX)
+
+        // We assume the first half is chunk(X_i) and the second half is always chunk(Y_0)
+        debug_assert!(point.len() % 2 == 0);
+
+        let log_WORD_SIZE = log2(WORD_SIZE) as usize;
+
+        let b = point.len() / 2;
+        let (x, y) = point.split_at(b);
+
+        // the most significant chunk might be shorter
+        let chunk_length = min(b, WORD_SIZE.saturating_sub(b * CHUNK_INDEX));
+
+        let mut result = F::zero();
+
+        // min with 1 << b is included for test cases with subtables of bit-length smaller than 6
+        for k in 0..min(WORD_SIZE, 1 << b) {
+            // bit-decompose k
+            let k_bits = k
+                .get_bits(log_WORD_SIZE)
+                .iter()
+                .map(|bit| if *bit { F::one() } else { F::zero() })
+                .collect::<Vec<F>>(); // big-endian
+
+            // Compute eq(y, bin(k))
+            let mut eq_term = F::one();
+            // again, min with b is included when subtables of bit-length less than 6 are used
+            for i in 0..min(log_WORD_SIZE, b) {
+                eq_term *= k_bits[log_WORD_SIZE - 1 - i] * y[b - 1 - i]
+                    + (F::one() - k_bits[log_WORD_SIZE - 1 - i]) * (F::one() - y[b - 1 - i]);
+            }
+
+            let rotate_x_by_k = (0..chunk_length)
+                .map(|j| {
+                    let position = (b * CHUNK_INDEX + j + WORD_SIZE - k) % WORD_SIZE;
+                    F::from_u64(1_u64 << position).unwrap() * x[b - 1 - j]
+                })
+                .fold(F::zero(), |acc, val: F| acc + val);
+
+            result += eq_term * rotate_x_by_k;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use binius_field::BinaryField128b;
+
+    use crate::{
+        field::binius::BiniusField,
+        jolt::subtable::{rotr::RotrSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(rotr_materialize_mle_parity0, RotrSubtable<Fr, 0, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(rotr_materialize_mle_parity1, RotrSubtable<Fr, 1, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(rotr_materialize_mle_parity2, RotrSubtable<Fr, 2, 32>, Fr, 1 << 10);
+    subtable_materialize_mle_parity_test!(rotr_materialize_mle_parity3, RotrSubtable<Fr, 3, 32>, Fr, 1 << 10);
+
+    subtable_materialize_mle_parity_test!(
+        rotr_binius_materialize_mle_parity3,
+        RotrSubtable<BiniusField<BinaryField128b>, 3, 32>,
+        BiniusField<BinaryField128b>,
+        1 << 10
+    );
+}
@@ -10,9 +10,10 @@ use crate::field::JoltField;
 use crate::jolt::instruction::JoltInstructionSet;
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use crate::poly::eq_poly::EqPolynomial;
+use crate::r1cs::jolt_constraints::NUM_INSTRUCTION_FLAGS;
 use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use common::constants::{BYTES_PER_INSTRUCTION, RAM_START_ADDRESS, REGISTER_COUNT};
+use common::constants::{ALIGNMENT_FACTOR_BYTECODE, RAM_START_ADDRESS, REGISTER_COUNT};
 use common::rv_trace::ELFInstruction;
 use common::to_ram_address;
 
@@ -101,11 +102,12 @@ impl BytecodeRow {
     /// The layout is:
     ///     circuit flags || instruction flags
     /// where instruction flags is a one-hot bitvector corresponding to the instruction's
-    /// index in the `InstructionSet` enum.
+    /// index in the `InstructionSet` enum, padded to `NUM_INSTRUCTION_FLAGS` bits.
     pub fn bitflags<InstructionSet>(instruction: &ELFInstruction) -> u64
     where
         InstructionSet: JoltInstructionSet,
     {
+        assert!(InstructionSet::COUNT <= NUM_INSTRUCTION_FLAGS);
         let mut bitvector = 0;
         for flag in instruction.to_circuit_flags() {
             bitvector |= flag as u64;
@@ -117,9 +119,9 @@ impl BytecodeRow {
             let instruction_index = InstructionSet::enum_index(&jolt_instruction);
             bitvector <<= instruction_index;
             bitvector |= 1;
-            bitvector <<= InstructionSet::COUNT - instruction_index - 1;
+            bitvector <<= NUM_INSTRUCTION_FLAGS - instruction_index - 1;
         } else {
-            bitvector <<= InstructionSet::COUNT - 1;
+            bitvector <<= NUM_INSTRUCTION_FLAGS - 1;
         }
 
         bitvector
@@ -190,10 +192,10 @@ impl<F: JoltField> BytecodePreprocessing<F> {
         let mut virtual_address = 1; // Account for no-op instruction prepended to bytecode
         for instruction in bytecode.iter_mut() {
             assert!(instruction.address >= RAM_START_ADDRESS as usize);
-            assert!(instruction.address % BYTES_PER_INSTRUCTION == 0);
+            assert!(instruction.address % ALIGNMENT_FACTOR_BYTECODE == 0);
             // Compress instruction address for more efficient commitment:
             instruction.address =
-                1 + (instruction.address - RAM_START_ADDRESS as usize) / BYTES_PER_INSTRUCTION;
+                1 + (instruction.address - RAM_START_ADDRESS as usize) / ALIGNMENT_FACTOR_BYTECODE;
             assert_eq!(
                 virtual_address_map.insert(
                     (
@@ -248,6 +250,65 @@ impl<F: JoltField> BytecodePreprocessing<F> {
     }
 }
 
+// `virtual_address_map` is serialized as a list of (key, value) pairs, sorted
+// by key so that the encoding is deterministic.
+impl<F: JoltField> CanonicalSerialize for BytecodePreprocessing<F> {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        self.code_size.serialize_with_mode(&mut writer, compress)?;
+        self.v_init_final
+            .serialize_with_mode(&mut writer, compress)?;
+        self.sorted_virtual_addresses()
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        self.code_size.serialized_size(compress)
+            + self.v_init_final.serialized_size(compress)
+            + self.sorted_virtual_addresses().serialized_size(compress)
+    }
+}
+
+impl<F: JoltField> CanonicalDeserialize for BytecodePreprocessing<F> {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let code_size = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let v_init_final =
+            <[DensePolynomial<F>; 6]>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let virtual_addresses =
+            Vec::<((usize, usize), usize)>::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self {
+            code_size,
+            v_init_final,
+            virtual_address_map: virtual_addresses.into_iter().collect(),
+        })
+    }
+}
+
+impl<F: JoltField> ark_serialize::Valid for BytecodePreprocessing<F> {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        ark_serialize::Valid::check(&self.v_init_final)
+    }
+}
+
+impl<F: JoltField> BytecodePreprocessing<F> {
+    fn sorted_virtual_addresses(&self) -> Vec<((usize, usize), usize)> {
+        let mut virtual_addresses: Vec<_> = self
+            .virtual_address_map
+            .iter()
+            .map(|(key, value)| (*key, *value))
+            .collect();
+        virtual_addresses.sort_unstable();
+        virtual_addresses
+    }
+}
+
 impl<F: JoltField, C: CommitmentScheme<Field = F>> BytecodePolynomials<F, C> {
     #[tracing::instrument(skip_all, name = "BytecodePolynomials::new")]
     pub fn new<InstructionSet: JoltInstructionSet>(
@@ -263,11 +324,11 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> BytecodePolynomials<F, C> {
         for (step_index, step) in trace.iter_mut().enumerate() {
             if !step.bytecode_row.address.is_zero() {
                 assert!(step.bytecode_row.address >= RAM_START_ADDRESS as usize);
-                assert!(step.bytecode_row.address % BYTES_PER_INSTRUCTION == 0);
+                assert!(step.bytecode_row.address % ALIGNMENT_FACTOR_BYTECODE == 0);
                 // Compress instruction address for more efficient commitment:
                 step.bytecode_row.address = 1
                     + (step.bytecode_row.address - RAM_START_ADDRESS as usize)
-                        / BYTES_PER_INSTRUCTION;
+                        / ALIGNMENT_FACTOR_BYTECODE;
             }
 
             let virtual_address = preprocessing
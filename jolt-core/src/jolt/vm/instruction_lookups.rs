@@ -796,6 +796,66 @@ impl<F: JoltField> InstructionLookupsPreprocessing<F> {
     }
 }
 
+// The materialized subtables are only used by the prover and make up the bulk of
+// the preprocessing, so they are not serialized. A deserialized
+// `InstructionLookupsPreprocessing` can therefore only be used for verification.
+impl<F: JoltField> CanonicalSerialize for InstructionLookupsPreprocessing<F> {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        self.subtable_to_memory_indices
+            .serialize_with_mode(&mut writer, compress)?;
+        self.instruction_to_memory_indices
+            .serialize_with_mode(&mut writer, compress)?;
+        self.memory_to_subtable_index
+            .serialize_with_mode(&mut writer, compress)?;
+        self.memory_to_dimension_index
+            .serialize_with_mode(&mut writer, compress)?;
+        self.num_memories.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        self.subtable_to_memory_indices.serialized_size(compress)
+            + self.instruction_to_memory_indices.serialized_size(compress)
+            + self.memory_to_subtable_index.serialized_size(compress)
+            + self.memory_to_dimension_index.serialized_size(compress)
+            + self.num_memories.serialized_size(compress)
+    }
+}
+
+impl<F: JoltField> CanonicalDeserialize for InstructionLookupsPreprocessing<F> {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        Ok(Self {
+            subtable_to_memory_indices: Vec::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            instruction_to_memory_indices: Vec::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            memory_to_subtable_index: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            memory_to_dimension_index: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            materialized_subtables: vec![],
+            num_memories: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<F: JoltField> ark_serialize::Valid for InstructionLookupsPreprocessing<F> {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
 impl<F, CS, InstructionSet, Subtables, const C: usize, const M: usize>
     InstructionLookupsProof<C, M, F, CS, InstructionSet, Subtables>
 where
@@ -2,7 +2,7 @@
 
 use crate::field::JoltField;
 use crate::r1cs::builder::CombinedUniformBuilder;
-use crate::r1cs::jolt_constraints::{construct_jolt_constraints, JoltIn};
+use crate::r1cs::jolt_constraints::{construct_jolt_constraints, JoltIn, NUM_INSTRUCTION_FLAGS};
 use crate::r1cs::spartan::{self, UniformSpartanProof};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::log2;
@@ -14,9 +14,20 @@ use strum::EnumCount;
 use crate::jolt::vm::timestamp_range_check::RangeCheckPolynomials;
 use crate::jolt::{
     instruction::{
-        div::DIVInstruction, divu::DIVUInstruction, mulh::MULHInstruction,
-        mulhsu::MULHSUInstruction, rem::REMInstruction, remu::REMUInstruction, JoltInstruction,
-        VirtualInstructionSequence,
+        atomic::{
+            AMOADDWInstruction, AMOANDWInstruction, AMOMAXUWInstruction, AMOMAXWInstruction,
+            AMOMINUWInstruction, AMOMINWInstruction, AMOORWInstruction, AMOSWAPWInstruction,
+            AMOXORWInstruction, LRWInstruction, SCWInstruction,
+        },
+        div::DIVInstruction,
+        divu::DIVUInstruction,
+        keccak::KeccakFInstruction,
+        mulh::MULHInstruction,
+        mulhsu::MULHSUInstruction,
+        rem::REMInstruction,
+        remu::REMUInstruction,
+        sha256::SHA256CompressInstruction,
+        JoltInstruction, VirtualInstructionSequence,
     },
     subtable::JoltSubtableSet,
     vm::timestamp_range_check::TimestampValidityProof,
@@ -61,6 +72,135 @@ where
     pub read_write_memory: ReadWriteMemoryPreprocessing,
 }
 
+impl<F, PCS> JoltPreprocessing<F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    /// Extracts the part of the preprocessing needed by `Jolt::verify`.
+    pub fn verifier_preprocessing(&self) -> JoltVerifierPreprocessing<F, PCS> {
+        JoltVerifierPreprocessing {
+            generators: PCS::verifier_setup(&self.generators),
+            instruction_lookups: self.instruction_lookups.clone(),
+            bytecode: self.bytecode.clone(),
+            read_write_memory: self.read_write_memory.clone(),
+        }
+    }
+}
+
+/// Version of the serialized `JoltVerifierPreprocessing` format. Bump this whenever
+/// the encoding of any of its components changes.
+pub const VERIFIER_PREPROCESSING_VERSION: u32 = 1;
+
+/// The preprocessing needed by `Jolt::verify`, in a form that can be serialized and loaded
+/// by a verifier without re-running `Jolt::preprocess`. Only contains the part of the PCS
+/// setup kept by `PCS::verifier_setup`, so the `JoltPreprocessing` it converts into is only
+/// meant for verifying. For HyperKZG and Zeromorph this is a small verifier key, but Hyrax
+/// openings are checked with MSMs over the commitment generators, so its verifier setup is
+/// the full set of Pedersen generators and no smaller than the prover's.
+#[derive(Clone)]
+pub struct JoltVerifierPreprocessing<F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    pub generators: PCS::VerifierSetup,
+    pub instruction_lookups: InstructionLookupsPreprocessing<F>,
+    pub bytecode: BytecodePreprocessing<F>,
+    pub read_write_memory: ReadWriteMemoryPreprocessing,
+}
+
+impl<F, PCS> From<JoltVerifierPreprocessing<F, PCS>> for JoltPreprocessing<F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    fn from(preprocessing: JoltVerifierPreprocessing<F, PCS>) -> Self {
+        Self {
+            generators: PCS::from_verifier_setup(preprocessing.generators),
+            instruction_lookups: preprocessing.instruction_lookups,
+            bytecode: preprocessing.bytecode,
+            read_write_memory: preprocessing.read_write_memory,
+        }
+    }
+}
+
+impl<F, PCS> CanonicalSerialize for JoltVerifierPreprocessing<F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        VERIFIER_PREPROCESSING_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.generators.serialize_with_mode(&mut writer, compress)?;
+        self.instruction_lookups
+            .serialize_with_mode(&mut writer, compress)?;
+        self.bytecode.serialize_with_mode(&mut writer, compress)?;
+        self.read_write_memory
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        VERIFIER_PREPROCESSING_VERSION.serialized_size(compress)
+            + self.generators.serialized_size(compress)
+            + self.instruction_lookups.serialized_size(compress)
+            + self.bytecode.serialized_size(compress)
+            + self.read_write_memory.serialized_size(compress)
+    }
+}
+
+impl<F, PCS> CanonicalDeserialize for JoltVerifierPreprocessing<F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let version = u32::deserialize_with_mode(&mut reader, compress, validate)?;
+        if version != VERIFIER_PREPROCESSING_VERSION {
+            return Err(ark_serialize::SerializationError::InvalidData);
+        }
+        Ok(Self {
+            generators: PCS::VerifierSetup::deserialize_with_mode(&mut reader, compress, validate)?,
+            instruction_lookups: InstructionLookupsPreprocessing::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            bytecode: BytecodePreprocessing::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            read_write_memory: ReadWriteMemoryPreprocessing::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+        })
+    }
+}
+
+impl<F, PCS> ark_serialize::Valid for JoltVerifierPreprocessing<F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        self.generators.check()?;
+        self.instruction_lookups.check()?;
+        self.bytecode.check()?;
+        self.read_write_memory.check()
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct JoltTraceStep<InstructionSet: JoltInstructionSet> {
     pub instruction_lookup: Option<InstructionSet>,
@@ -294,6 +434,27 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
                 tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_sequence(instruction),
                 tracer::RV32IM::REM => REMInstruction::<32>::virtual_sequence(instruction),
                 tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::LR_W => LRWInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::SC_W => SCWInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::AMOSWAP_W => {
+                    AMOSWAPWInstruction::<32>::virtual_sequence(instruction)
+                }
+                tracer::RV32IM::AMOADD_W => AMOADDWInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::AMOXOR_W => AMOXORWInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::AMOAND_W => AMOANDWInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::AMOOR_W => AMOORWInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::AMOMIN_W => AMOMINWInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::AMOMAX_W => AMOMAXWInstruction::<32>::virtual_sequence(instruction),
+                tracer::RV32IM::AMOMINU_W => {
+                    AMOMINUWInstruction::<32>::virtual_sequence(instruction)
+                }
+                tracer::RV32IM::AMOMAXU_W => {
+                    AMOMAXUWInstruction::<32>::virtual_sequence(instruction)
+                }
+                tracer::RV32IM::SHA256_COMPRESS => {
+                    SHA256CompressInstruction::<32>::virtual_sequence(instruction)
+                }
+                tracer::RV32IM::KECCAK_F => KeccakFInstruction::<32>::virtual_sequence(instruction),
                 _ => vec![instruction],
             })
             .map(|instruction| BytecodeRow::from_instruction::<Self::InstructionSet>(&instruction))
@@ -527,8 +688,14 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         program_io: JoltDevice,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        assert!(program_io.inputs.len() <= program_io.memory_layout.max_input_size as usize);
-        assert!(program_io.outputs.len() <= program_io.memory_layout.max_output_size as usize);
+        if program_io.inputs.len() > program_io.memory_layout.max_input_size as usize
+            || program_io.outputs.len() > program_io.memory_layout.max_output_size as usize
+        {
+            return Err(ProofVerifyError::InputTooLarge);
+        }
+        if !program_io.termination {
+            return Err(ProofVerifyError::ProgramNotTerminated);
+        }
         preprocessing.program_io = Some(program_io);
 
         ReadWriteMemoryProof::verify(proof, generators, preprocessing, commitment, transcript)
@@ -541,8 +708,22 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         commitments: JoltCommitments<PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
+        let num_unused_instruction_flags = commitments
+            .r1cs
+            .as_ref()
+            .map(|r1cs| r1cs.unused_instruction_flags.len());
+        if num_unused_instruction_flags != Some(NUM_INSTRUCTION_FLAGS - Self::InstructionSet::COUNT)
+        {
+            return Err(ProofVerifyError::InternalError);
+        }
         proof
-            .verify(generators, commitments, C, transcript)
+            .verify(
+                generators,
+                commitments,
+                C,
+                Self::InstructionSet::COUNT,
+                transcript,
+            )
             .map_err(|e| ProofVerifyError::SpartanError(e.to_string()))
     }
 
@@ -587,6 +768,15 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             generators,
             BatchType::Big,
         );
+        let unused_instruction_flags: Vec<&[F]> = inputs.instruction_flags_bits
+            [Self::InstructionSet::COUNT * padded_trace_length..]
+            .chunks(padded_trace_length)
+            .collect();
+        let unused_instruction_flags_comms = if unused_instruction_flags.is_empty() {
+            vec![]
+        } else {
+            PCS::batch_commit(&unused_instruction_flags, generators, BatchType::Big)
+        };
         drop(_guard);
 
         let io_comms = PCS::batch_commit(&[inputs.pc.as_ref()], generators, BatchType::Big);
@@ -601,6 +791,7 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             aux: aux_comms,
             chunks: chunks_comms,
             circuit_flags: circuit_flags_comms,
+            unused_instruction_flags: unused_instruction_flags_comms,
         };
 
         #[cfg(test)]
@@ -658,8 +849,9 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
 
         let span = tracing::span!(tracing::Level::INFO, "flatten_instruction_flags");
         let _enter = span.enter();
-        let instruction_flags: Vec<F> =
+        let mut instruction_flags: Vec<F> =
             DensePolynomial::flatten(&polynomials.instruction_lookups.instruction_flag_polys);
+        instruction_flags.resize(NUM_INSTRUCTION_FLAGS * padded_trace_length, F::zero());
         drop(_enter);
         drop(span);
 
@@ -714,6 +906,8 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         transcript.append_bytes(&program_io.inputs);
         transcript.append_bytes(&program_io.outputs);
         transcript.append_u64(program_io.panic as u64);
+        transcript.append_u64(program_io.termination as u64);
+        transcript.append_bytes(&program_io.panic_info);
     }
 }
 
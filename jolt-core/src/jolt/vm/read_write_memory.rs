@@ -173,7 +173,7 @@ pub fn random_memory_trace<F: JoltField>(
     )
 }
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ReadWriteMemoryPreprocessing {
     min_bytecode_address: u64,
     pub bytecode_bytes: Vec<u8>,
@@ -1511,10 +1511,16 @@ where
         let r_eq = transcript.challenge_vector(num_rounds);
         let eq: DensePolynomial<F> = DensePolynomial::new(EqPolynomial::evals(&r_eq));
 
+        // The guest's log is not part of the program IO
+        let log_index = memory_address_to_witness_index(
+            program_io.memory_layout.log,
+            program_io.memory_layout.ram_witness_offset,
+        ) as u64;
         let io_witness_range: Vec<_> = (0..polynomials.memory_size as u64)
             .map(|i| {
                 if i >= program_io.memory_layout.input_start
                     && i < program_io.memory_layout.ram_witness_offset
+                    && i != log_index
                 {
                     F::one()
                 } else {
@@ -1547,6 +1553,20 @@ where
             program_io.memory_layout.panic,
             program_io.memory_layout.ram_witness_offset,
         )] = program_io.panic as u64;
+        // Copy termination bit
+        v_io[memory_address_to_witness_index(
+            program_io.memory_layout.termination,
+            program_io.memory_layout.ram_witness_offset,
+        )] = program_io.termination as u64;
+        // Copy panic info bytes
+        let mut panic_info_index = memory_address_to_witness_index(
+            program_io.memory_layout.panic_info_start,
+            program_io.memory_layout.ram_witness_offset,
+        );
+        for byte in program_io.panic_info.iter() {
+            v_io[panic_info_index] = *byte as u64;
+            panic_info_index += 1;
+        }
 
         let mut sumcheck_polys = vec![
             eq,
@@ -1604,9 +1624,13 @@ where
             "Ram witness offset must be a power of two"
         );
 
+        // The guest's log is not part of the program IO
+        let log_index =
+            memory_address_to_witness_index(memory_layout.log, memory_layout.ram_witness_offset)
+                as u64;
         let io_witness_range: Vec<_> = (0..nonzero_memory_size as u64)
             .map(|i| {
-                if i >= memory_layout.input_start {
+                if i >= memory_layout.input_start && i != log_index {
                     F::one()
                 } else {
                     F::zero()
@@ -1643,6 +1667,20 @@ where
             memory_layout.panic,
             memory_layout.ram_witness_offset,
         )] = preprocessing.program_io.as_ref().unwrap().panic as u64;
+        // Copy termination bit
+        v_io[memory_address_to_witness_index(
+            memory_layout.termination,
+            memory_layout.ram_witness_offset,
+        )] = preprocessing.program_io.as_ref().unwrap().termination as u64;
+        // Copy panic info bytes
+        let mut panic_info_index = memory_address_to_witness_index(
+            memory_layout.panic_info_start,
+            memory_layout.ram_witness_offset,
+        );
+        for byte in preprocessing.program_io.as_ref().unwrap().panic_info.iter() {
+            v_io[panic_info_index] = *byte as u64;
+            panic_info_index += 1;
+        }
         let mut v_io_eval =
             DensePolynomial::from_u64(&v_io).evaluate(&r_sumcheck[..log_nonzero_memory_size]);
         v_io_eval *= r_prod;
@@ -1,160 +1,510 @@
 use crate::field::JoltField;
-use crate::jolt::instruction::virtual_assert_valid_div0::AssertValidDiv0Instruction;
-use crate::jolt::instruction::virtual_assert_valid_unsigned_remainder::AssertValidUnsignedRemainderInstruction;
-use crate::jolt::instruction::virtual_move::MOVEInstruction;
-use crate::jolt::subtable::div_by_zero::DivByZeroSubtable;
-use crate::jolt::subtable::right_is_zero::RightIsZeroSubtable;
+use crate::poly::commitment::hyperkzg::HyperKZG;
 use crate::poly::commitment::hyrax::HyraxScheme;
-use ark_bn254::{Fr, G1Projective};
+use crate::poly::commitment::zeromorph::Zeromorph;
+use ark_bn254::{Bn254, Fr, G1Projective};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use enum_dispatch::enum_dispatch;
-use rand::{prelude::StdRng, RngCore};
-use serde::{Deserialize, Serialize};
-use std::any::TypeId;
-use strum::{EnumCount, IntoEnumIterator};
-use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
-
-use super::{Jolt, JoltCommitments, JoltProof};
-use crate::jolt::instruction::{
-    add::ADDInstruction, and::ANDInstruction, beq::BEQInstruction, bge::BGEInstruction,
-    bgeu::BGEUInstruction, bne::BNEInstruction, lb::LBInstruction, lh::LHInstruction,
-    mul::MULInstruction, mulhu::MULHUInstruction, mulu::MULUInstruction, or::ORInstruction,
-    sb::SBInstruction, sh::SHInstruction, sll::SLLInstruction, slt::SLTInstruction,
-    sltu::SLTUInstruction, sra::SRAInstruction, srl::SRLInstruction, sub::SUBInstruction,
-    sw::SWInstruction, virtual_advice::ADVICEInstruction, virtual_assert_lte::ASSERTLTEInstruction,
-    virtual_assert_valid_signed_remainder::AssertValidSignedRemainderInstruction,
-    virtual_movsign::MOVSIGNInstruction, xor::XORInstruction, JoltInstruction, JoltInstructionSet,
-    SubtableIndices,
-};
-use crate::jolt::subtable::{
-    and::AndSubtable, eq::EqSubtable, eq_abs::EqAbsSubtable, identity::IdentitySubtable,
-    left_is_zero::LeftIsZeroSubtable, left_msb::LeftMSBSubtable, lt_abs::LtAbsSubtable,
-    ltu::LtuSubtable, or::OrSubtable, right_msb::RightMSBSubtable, sign_extend::SignExtendSubtable,
-    sll::SllSubtable, sra_sign::SraSignSubtable, srl::SrlSubtable,
-    truncate_overflow::TruncateOverflowSubtable, xor::XorSubtable, JoltSubtableSet, LassoSubtable,
-    SubtableId,
-};
+
+use super::{Jolt, JoltCommitments, JoltProof, JoltVerifierPreprocessing};
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 
 /// Generates an enum out of a list of JoltInstruction types. All JoltInstruction methods
-/// are callable on the enum type via enum_dispatch.
+/// are callable on the enum type, which dispatches them to its variants. Instructions
+/// listed after a `;` implement `JoltCustomInstruction`, and are the instruction set's
+/// custom instructions.
+#[macro_export]
 macro_rules! instruction_set {
     ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
-        #[allow(non_camel_case_types)]
+        $crate::instruction_set!(@enum $enum_name, $($alias: $struct),+);
+        impl $crate::jolt::instruction::JoltInstructionSet for $enum_name {}
+    };
+    (
+        $enum_name:ident,
+        $($alias:ident: $struct:ty),+;
+        $($custom_alias:ident: $custom_struct:ty),+
+    ) => {
+        $crate::instruction_set!(
+            @enum $enum_name,
+            $($alias: $struct),+,
+            $($custom_alias: $custom_struct),+
+        );
+        const _: () = assert!(
+            [$(stringify!($alias)),+, $(stringify!($custom_alias)),+].len()
+                <= $crate::r1cs::jolt_constraints::NUM_INSTRUCTION_FLAGS,
+            concat!(
+                stringify!($enum_name),
+                " has more instructions than the R1CS has instruction flags, so at most ",
+                "NUM_CUSTOM_INSTRUCTION_FLAGS custom instructions can extend RV32I",
+            ),
+        );
+        impl $crate::jolt::instruction::JoltInstructionSet for $enum_name {
+            fn custom_instructions() -> Vec<$crate::jolt::instruction::CustomInstruction> {
+                vec![$(
+                    <$custom_struct as $crate::jolt::instruction::JoltCustomInstruction>
+                        ::tracer_instruction()
+                ),+]
+            }
+        }
+    };
+    (@enum $enum_name:ident, $($alias:ident: $struct:ty),+) => {
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[repr(u8)]
-        #[derive(Copy, Clone, Debug, EnumIter, EnumCountMacro, Serialize, Deserialize)]
-        #[enum_dispatch(JoltInstruction)]
+        #[derive(Copy, Clone, Debug)]
         pub enum $enum_name { $($alias($struct)),+ }
-        impl JoltInstructionSet for $enum_name {}
-        impl $enum_name {
-            pub fn random_instruction(rng: &mut StdRng) -> Self {
-                let index = rng.next_u64() as usize % $enum_name::COUNT;
-                let instruction = $enum_name::iter()
-                    .enumerate()
-                    .filter(|(i, _)| *i == index)
-                    .map(|(_, x)| x)
-                    .next()
-                    .unwrap();
-                instruction.random(rng)
+        $(
+            impl From<$struct> for $enum_name {
+                fn from(instruction: $struct) -> Self {
+                    $enum_name::$alias(instruction)
+                }
             }
-        }
+        )+
+        $crate::impl_enum_iter!($enum_name, $($alias),+);
+        $crate::impl_instruction_set_serde!($enum_name, $($alias: $struct),+);
+        const _: () = {
+            use $crate::__private::rand::{prelude::StdRng, RngCore};
+            use $crate::__private::strum::{EnumCount, IntoEnumIterator};
+            use $crate::field::JoltField;
+            use $crate::jolt::instruction::{JoltInstruction, SubtableIndices};
+            use $crate::jolt::subtable::LassoSubtable;
+
+            impl $enum_name {
+                pub fn random_instruction(rng: &mut StdRng) -> Self {
+                    let index = rng.next_u64() as usize % $enum_name::COUNT;
+                    let instruction = $enum_name::iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i == index)
+                        .map(|(_, x)| x)
+                        .next()
+                        .unwrap();
+                    instruction.random(rng)
+                }
+            }
+            // Dispatched by hand rather than with `enum_dispatch`, so that the macro can be
+            // invoked for more than one instruction set.
+            impl JoltInstruction for $enum_name {
+                fn operands(&self) -> (u64, u64) {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.operands()),+
+                    }
+                }
+                fn combine_lookups<F: JoltField>(&self, vals: &[F], c: usize, m: usize) -> F {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.combine_lookups(vals, c, m)),+
+                    }
+                }
+                fn g_poly_degree(&self, c: usize) -> usize {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.g_poly_degree(c)),+
+                    }
+                }
+                fn subtables<F: JoltField>(
+                    &self,
+                    c: usize,
+                    m: usize,
+                ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.subtables(c, m)),+
+                    }
+                }
+                fn to_indices(&self, c: usize, log_m: usize) -> Vec<usize> {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.to_indices(c, log_m)),+
+                    }
+                }
+                fn lookup_entry(&self) -> u64 {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.lookup_entry()),+
+                    }
+                }
+                fn operand_chunks(&self, c: usize, log_m: usize) -> (Vec<u64>, Vec<u64>) {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.operand_chunks(c, log_m)),+
+                    }
+                }
+                fn random(&self, rng: &mut StdRng) -> Self {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.random(rng).into()),+
+                    }
+                }
+                fn slice_values<'a, F: JoltField>(
+                    &self,
+                    vals: &'a [F],
+                    c: usize,
+                    m: usize,
+                ) -> Vec<&'a [F]> {
+                    match self {
+                        $($enum_name::$alias(instruction) => instruction.slice_values(vals, c, m)),+
+                    }
+                }
+            }
+        };
     };
 }
 
 /// Generates an enum out of a list of LassoSubtable types. All LassoSubtable methods
-/// are callable on the enum type via enum_dispatch.
+/// are callable on the enum type, which dispatches them to its variants.
+#[macro_export]
 macro_rules! subtable_enum {
     ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
-        #[allow(non_camel_case_types)]
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[repr(u8)]
-        #[enum_dispatch(LassoSubtable<F>)]
-        #[derive(EnumCountMacro, EnumIter)]
-        pub enum $enum_name<F: JoltField> { $($alias($struct)),+ }
-        impl<F: JoltField> From<SubtableId> for $enum_name<F> {
-          fn from(subtable_id: SubtableId) -> Self {
+        pub enum $enum_name<F: $crate::field::JoltField> { $($alias($struct)),+ }
+        $crate::impl_enum_iter!($enum_name<F>, $($alias),+);
+        const _: () = {
+            use $crate::field::JoltField;
+            use $crate::jolt::subtable::{JoltSubtableSet, LassoSubtable, SubtableId};
+            use ::std::any::TypeId;
+
+            impl<F: JoltField> From<SubtableId> for $enum_name<F> {
+              fn from(subtable_id: SubtableId) -> Self {
+                $(
+                  if subtable_id == TypeId::of::<$struct>() {
+                    $enum_name::from(<$struct>::new())
+                  } else
+                )+
+                { panic!("Unexpected subtable id {:?}", subtable_id) } // TODO(moodlezoup): better error handling
+              }
+            }
+
+            impl<F: JoltField> From<$enum_name<F>> for usize {
+                fn from(subtable: $enum_name<F>) -> usize {
+                    // Discriminant: https://doc.rust-lang.org/reference/items/enumerations.html#pointer-casting
+                    let byte = unsafe { *(&subtable as *const $enum_name<F> as *const u8) };
+                    byte as usize
+                }
+            }
+            impl<F: JoltField> From<&$enum_name<F>> for &'static str {
+                fn from(subtable: &$enum_name<F>) -> &'static str {
+                    match subtable {
+                        $($enum_name::$alias(_) => stringify!($alias)),+
+                    }
+                }
+            }
+            impl<F: JoltField> From<$enum_name<F>> for &'static str {
+                fn from(subtable: $enum_name<F>) -> &'static str {
+                    (&subtable).into()
+                }
+            }
+            impl<F: JoltField> JoltSubtableSet<F> for $enum_name<F> {}
             $(
-              if subtable_id == TypeId::of::<$struct>() {
-                $enum_name::from(<$struct>::new())
-              } else
+                impl<F: JoltField> From<$struct> for $enum_name<F> {
+                    fn from(subtable: $struct) -> Self {
+                        $enum_name::$alias(subtable)
+                    }
+                }
             )+
-            { panic!("Unexpected subtable id {:?}", subtable_id) } // TODO(moodlezoup): better error handling
-          }
+            impl<F: JoltField> LassoSubtable<F> for $enum_name<F> {
+                fn subtable_id(&self) -> SubtableId {
+                    match self {
+                        $($enum_name::$alias(subtable) => LassoSubtable::<F>::subtable_id(subtable)),+
+                    }
+                }
+                fn materialize(&self, m: usize) -> Vec<F> {
+                    match self {
+                        $($enum_name::$alias(subtable) => subtable.materialize(m)),+
+                    }
+                }
+                fn evaluate_mle(&self, point: &[F]) -> F {
+                    match self {
+                        $($enum_name::$alias(subtable) => subtable.evaluate_mle(point)),+
+                    }
+                }
+            }
+        };
+    };
+}
+
+/// Invokes `$callback!($($args)* <RV32I instructions> $($rest)*)`, where the instructions
+/// are listed as `ALIAS: Type` pairs in the order of the `RV32I` variants.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! with_rv32i_instructions {
+    ($callback:ident!($($args:tt)*) $($rest:tt)*) => {
+        $crate::$callback!(
+            $($args)*
+            ADD: $crate::jolt::instruction::add::ADDInstruction<32>,
+            SUB: $crate::jolt::instruction::sub::SUBInstruction<32>,
+            AND: $crate::jolt::instruction::and::ANDInstruction<32>,
+            OR: $crate::jolt::instruction::or::ORInstruction<32>,
+            XOR: $crate::jolt::instruction::xor::XORInstruction<32>,
+            LB: $crate::jolt::instruction::lb::LBInstruction<32>,
+            LH: $crate::jolt::instruction::lh::LHInstruction<32>,
+            SB: $crate::jolt::instruction::sb::SBInstruction<32>,
+            SH: $crate::jolt::instruction::sh::SHInstruction<32>,
+            SW: $crate::jolt::instruction::sw::SWInstruction<32>,
+            BEQ: $crate::jolt::instruction::beq::BEQInstruction<32>,
+            BGE: $crate::jolt::instruction::bge::BGEInstruction<32>,
+            BGEU: $crate::jolt::instruction::bgeu::BGEUInstruction<32>,
+            BNE: $crate::jolt::instruction::bne::BNEInstruction<32>,
+            SLT: $crate::jolt::instruction::slt::SLTInstruction<32>,
+            SLTU: $crate::jolt::instruction::sltu::SLTUInstruction<32>,
+            SLL: $crate::jolt::instruction::sll::SLLInstruction<32>,
+            SRA: $crate::jolt::instruction::sra::SRAInstruction<32>,
+            SRL: $crate::jolt::instruction::srl::SRLInstruction<32>,
+            MOVSIGN: $crate::jolt::instruction::virtual_movsign::MOVSIGNInstruction<32>,
+            MUL: $crate::jolt::instruction::mul::MULInstruction<32>,
+            MULU: $crate::jolt::instruction::mulu::MULUInstruction<32>,
+            MULHU: $crate::jolt::instruction::mulhu::MULHUInstruction<32>,
+            VIRTUAL_ADVICE: $crate::jolt::instruction::virtual_advice::ADVICEInstruction<32>,
+            VIRTUAL_MOVE: $crate::jolt::instruction::virtual_move::MOVEInstruction<32>,
+            VIRTUAL_ASSERT_LTE: $crate::jolt::instruction::virtual_assert_lte::ASSERTLTEInstruction<32>,
+            VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER: $crate::jolt::instruction::virtual_assert_valid_signed_remainder::AssertValidSignedRemainderInstruction<32>,
+            VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER: $crate::jolt::instruction::virtual_assert_valid_unsigned_remainder::AssertValidUnsignedRemainderInstruction<32>,
+            VIRTUAL_ASSERT_VALID_DIV0: $crate::jolt::instruction::virtual_assert_valid_div0::AssertValidDiv0Instruction<32>,
+            VIRTUAL_ROTR: $crate::jolt::instruction::virtual_rotr::ROTRInstruction<32>
+            $($rest)*
+        );
+    };
+}
+
+/// Invokes `$callback!($($args)* <RV32I subtables> $($rest)*)`, where the subtables are
+/// listed as `ALIAS: Type` pairs in the order of the `RV32ISubtables` variants.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! with_rv32i_subtables {
+    ($callback:ident!($($args:tt)*) $($rest:tt)*) => {
+        $crate::$callback!(
+            $($args)*
+            AND: $crate::jolt::subtable::and::AndSubtable<F>,
+            EQ_ABS: $crate::jolt::subtable::eq_abs::EqAbsSubtable<F>,
+            EQ: $crate::jolt::subtable::eq::EqSubtable<F>,
+            LEFT_MSB: $crate::jolt::subtable::left_msb::LeftMSBSubtable<F>,
+            RIGHT_MSB: $crate::jolt::subtable::right_msb::RightMSBSubtable<F>,
+            IDENTITY: $crate::jolt::subtable::identity::IdentitySubtable<F>,
+            LT_ABS: $crate::jolt::subtable::lt_abs::LtAbsSubtable<F>,
+            LTU: $crate::jolt::subtable::ltu::LtuSubtable<F>,
+            OR: $crate::jolt::subtable::or::OrSubtable<F>,
+            SIGN_EXTEND_8: $crate::jolt::subtable::sign_extend::SignExtendSubtable<F, 8>,
+            SIGN_EXTEND_16: $crate::jolt::subtable::sign_extend::SignExtendSubtable<F, 16>,
+            SLL0: $crate::jolt::subtable::sll::SllSubtable<F, 0, 32>,
+            SLL1: $crate::jolt::subtable::sll::SllSubtable<F, 1, 32>,
+            SLL2: $crate::jolt::subtable::sll::SllSubtable<F, 2, 32>,
+            SLL3: $crate::jolt::subtable::sll::SllSubtable<F, 3, 32>,
+            SRA_SIGN: $crate::jolt::subtable::sra_sign::SraSignSubtable<F, 32>,
+            SRL0: $crate::jolt::subtable::srl::SrlSubtable<F, 0, 32>,
+            SRL1: $crate::jolt::subtable::srl::SrlSubtable<F, 1, 32>,
+            SRL2: $crate::jolt::subtable::srl::SrlSubtable<F, 2, 32>,
+            SRL3: $crate::jolt::subtable::srl::SrlSubtable<F, 3, 32>,
+            ROTR0: $crate::jolt::subtable::rotr::RotrSubtable<F, 0, 32>,
+            ROTR1: $crate::jolt::subtable::rotr::RotrSubtable<F, 1, 32>,
+            ROTR2: $crate::jolt::subtable::rotr::RotrSubtable<F, 2, 32>,
+            ROTR3: $crate::jolt::subtable::rotr::RotrSubtable<F, 3, 32>,
+            TRUNCATE: $crate::jolt::subtable::truncate_overflow::TruncateOverflowSubtable<F, 32>,
+            TRUNCATE_BYTE: $crate::jolt::subtable::truncate_overflow::TruncateOverflowSubtable<F, 8>,
+            XOR: $crate::jolt::subtable::xor::XorSubtable<F>,
+            LEFT_IS_ZERO: $crate::jolt::subtable::left_is_zero::LeftIsZeroSubtable<F>,
+            RIGHT_IS_ZERO: $crate::jolt::subtable::right_is_zero::RightIsZeroSubtable<F>,
+            DIV_BY_ZERO: $crate::jolt::subtable::div_by_zero::DivByZeroSubtable<F>
+            $($rest)*
+        );
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_from_rv32i {
+    ($enum_name:ident; $($alias:ident: $struct:ty),+) => {
+        impl From<$crate::jolt::vm::rv32i_vm::RV32I> for $enum_name {
+            fn from(instruction: $crate::jolt::vm::rv32i_vm::RV32I) -> Self {
+                match instruction {
+                    $($crate::jolt::vm::rv32i_vm::RV32I::$alias(instruction) => $enum_name::$alias(instruction)),+
+                }
+            }
         }
+    };
+}
 
-        impl<F: JoltField> From<$enum_name<F>> for usize {
-            fn from(subtable: $enum_name<F>) -> usize {
-                // Discriminant: https://doc.rust-lang.org/reference/items/enumerations.html#pointer-casting
-                let byte = unsafe { *(&subtable as *const $enum_name<F> as *const u8) };
-                byte as usize
+/// Implements strum's `EnumCount` and `IntoEnumIterator` for an enum generated by
+/// `instruction_set!` or `subtable_enum!`, as the strum derives would require the invoking
+/// crate to depend on strum. Variants are iterated with default values.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_enum_iter {
+    ($enum_name:ident, $($alias:ident),+) => {
+        $crate::impl_enum_iter!(@impl [] $enum_name [], $($alias),+);
+    };
+    ($enum_name:ident<F>, $($alias:ident),+) => {
+        $crate::impl_enum_iter!(@impl [F: $crate::field::JoltField] $enum_name [F], $($alias),+);
+    };
+    (@impl [$($generics:tt)*] $enum_name:ident [$($args:tt)*], $($alias:ident),+) => {
+        impl<$($generics)*> $crate::__private::strum::EnumCount for $enum_name<$($args)*> {
+            const COUNT: usize = [$(stringify!($alias)),+].len();
+        }
+        impl<$($generics)*> $crate::__private::strum::IntoEnumIterator for $enum_name<$($args)*> {
+            type Iterator = ::std::vec::IntoIter<Self>;
+
+            fn iter() -> Self::Iterator {
+                vec![$($enum_name::$alias(::std::default::Default::default())),+].into_iter()
             }
         }
-        impl<F: JoltField> JoltSubtableSet<F> for $enum_name<F> {}
     };
 }
 
-const WORD_SIZE: usize = 32;
-
-instruction_set!(
-  RV32I,
-  ADD: ADDInstruction<WORD_SIZE>,
-  SUB: SUBInstruction<WORD_SIZE>,
-  AND: ANDInstruction<WORD_SIZE>,
-  OR: ORInstruction<WORD_SIZE>,
-  XOR: XORInstruction<WORD_SIZE>,
-  LB: LBInstruction<WORD_SIZE>,
-  LH: LHInstruction<WORD_SIZE>,
-  SB: SBInstruction<WORD_SIZE>,
-  SH: SHInstruction<WORD_SIZE>,
-  SW: SWInstruction<WORD_SIZE>,
-  BEQ: BEQInstruction<WORD_SIZE>,
-  BGE: BGEInstruction<WORD_SIZE>,
-  BGEU: BGEUInstruction<WORD_SIZE>,
-  BNE: BNEInstruction<WORD_SIZE>,
-  SLT: SLTInstruction<WORD_SIZE>,
-  SLTU: SLTUInstruction<WORD_SIZE>,
-  SLL: SLLInstruction<WORD_SIZE>,
-  SRA: SRAInstruction<WORD_SIZE>,
-  SRL: SRLInstruction<WORD_SIZE>,
-  MOVSIGN: MOVSIGNInstruction<WORD_SIZE>,
-  MUL: MULInstruction<WORD_SIZE>,
-  MULU: MULUInstruction<WORD_SIZE>,
-  MULHU: MULHUInstruction<WORD_SIZE>,
-  VIRTUAL_ADVICE: ADVICEInstruction<WORD_SIZE>,
-  VIRTUAL_MOVE: MOVEInstruction<WORD_SIZE>,
-  VIRTUAL_ASSERT_LTE: ASSERTLTEInstruction<WORD_SIZE>,
-  VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER: AssertValidSignedRemainderInstruction<WORD_SIZE>,
-  VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER: AssertValidUnsignedRemainderInstruction<WORD_SIZE>,
-  VIRTUAL_ASSERT_VALID_DIV0: AssertValidDiv0Instruction<WORD_SIZE>
-);
-subtable_enum!(
-  RV32ISubtables,
-  AND: AndSubtable<F>,
-  EQ_ABS: EqAbsSubtable<F>,
-  EQ: EqSubtable<F>,
-  LEFT_MSB: LeftMSBSubtable<F>,
-  RIGHT_MSB: RightMSBSubtable<F>,
-  IDENTITY: IdentitySubtable<F>,
-  LT_ABS: LtAbsSubtable<F>,
-  LTU: LtuSubtable<F>,
-  OR: OrSubtable<F>,
-  SIGN_EXTEND_8: SignExtendSubtable<F, 8>,
-  SIGN_EXTEND_16: SignExtendSubtable<F, 16>,
-  SLL0: SllSubtable<F, 0, WORD_SIZE>,
-  SLL1: SllSubtable<F, 1, WORD_SIZE>,
-  SLL2: SllSubtable<F, 2, WORD_SIZE>,
-  SLL3: SllSubtable<F, 3, WORD_SIZE>,
-  SRA_SIGN: SraSignSubtable<F, WORD_SIZE>,
-  SRL0: SrlSubtable<F, 0, WORD_SIZE>,
-  SRL1: SrlSubtable<F, 1, WORD_SIZE>,
-  SRL2: SrlSubtable<F, 2, WORD_SIZE>,
-  SRL3: SrlSubtable<F, 3, WORD_SIZE>,
-  TRUNCATE: TruncateOverflowSubtable<F, WORD_SIZE>,
-  TRUNCATE_BYTE: TruncateOverflowSubtable<F, 8>,
-  XOR: XorSubtable<F>,
-  LEFT_IS_ZERO: LeftIsZeroSubtable<F>,
-  RIGHT_IS_ZERO: RightIsZeroSubtable<F>,
-  DIV_BY_ZERO: DivByZeroSubtable<F>
-);
+/// Implements serde's `Serialize` and `Deserialize` for an enum generated by
+/// `instruction_set!`, in the format of the serde derives, which would require the invoking
+/// crate to depend on serde.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_instruction_set_serde {
+    ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
+        const _: () = {
+            use $crate::__private::serde::de::{self, EnumAccess, VariantAccess};
+            use $crate::__private::serde::{Deserialize, Deserializer, Serialize, Serializer};
+            use $crate::jolt::instruction::JoltInstructionSet;
+            use ::std::fmt;
+
+            const VARIANTS: &[&str] = &[$(stringify!($alias)),+];
+
+            impl Serialize for $enum_name {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let index = Self::enum_index(self) as u32;
+                    match self {
+                        $($enum_name::$alias(instruction) => serializer.serialize_newtype_variant(
+                            stringify!($enum_name),
+                            index,
+                            stringify!($alias),
+                            instruction,
+                        )),+
+                    }
+                }
+            }
+
+            /// The index of a variant, deserialized from either its index or its name.
+            struct Variant(usize);
+
+            impl<'de> Deserialize<'de> for Variant {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_identifier(VariantVisitor)
+                }
+            }
+
+            struct VariantVisitor;
+
+            impl<'de> de::Visitor<'de> for VariantVisitor {
+                type Value = Variant;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("variant identifier")
+                }
+
+                fn visit_u64<E: de::Error>(self, value: u64) -> Result<Variant, E> {
+                    if (value as usize) < VARIANTS.len() {
+                        Ok(Variant(value as usize))
+                    } else {
+                        Err(E::invalid_value(de::Unexpected::Unsigned(value), &self))
+                    }
+                }
+
+                fn visit_str<E: de::Error>(self, value: &str) -> Result<Variant, E> {
+                    VARIANTS
+                        .iter()
+                        .position(|variant| *variant == value)
+                        .map(Variant)
+                        .ok_or_else(|| E::unknown_variant(value, VARIANTS))
+                }
+            }
+
+            struct Visitor;
+
+            impl<'de> de::Visitor<'de> for Visitor {
+                type Value = $enum_name;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str(concat!("enum ", stringify!($enum_name)))
+                }
+
+                fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                    let (Variant(index), variant) = data.variant()?;
+                    match VARIANTS[index] {
+                        $(stringify!($alias) => variant.newtype_variant::<$struct>().map($enum_name::$alias),)+
+                        _ => unreachable!(),
+                    }
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $enum_name {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_enum(stringify!($enum_name), VARIANTS, Visitor)
+                }
+            }
+        };
+    };
+}
+
+/// Generates an instruction set extending `RV32I` with a list of `JoltCustomInstruction`
+/// types, e.g.
+///
+/// ```ignore
+/// custom_instruction_set!(MyInstructionSet, ANDN: ANDNInstruction);
+/// ```
+///
+/// The custom instructions follow the RV32I instructions, and there can be at most
+/// `NUM_CUSTOM_INSTRUCTION_FLAGS` of them, which is checked at compile time. Custom
+/// instructions are matched against bytecode and trace rows by their opcode and funct
+/// fields; every other instruction is converted as by `RV32I`.
+#[macro_export]
+macro_rules! custom_instruction_set {
+    ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
+        $crate::with_rv32i_instructions!(instruction_set!($enum_name,); $($alias: $struct),+);
+        $crate::with_rv32i_instructions!(impl_from_rv32i!($enum_name;));
+
+        impl TryFrom<&$crate::jolt::instruction::ELFInstruction> for $enum_name {
+            type Error = &'static str;
+
+            fn try_from(
+                instruction: &$crate::jolt::instruction::ELFInstruction,
+            ) -> Result<Self, Self::Error> {
+                $(
+                    if <$struct as $crate::jolt::instruction::JoltCustomInstruction>::matches(
+                        instruction,
+                    ) {
+                        return Ok(<$struct>::default().into());
+                    }
+                )+
+                $crate::jolt::vm::rv32i_vm::RV32I::try_from(instruction).map(Self::from)
+            }
+        }
+
+        impl TryFrom<&$crate::jolt::instruction::RVTraceRow> for $enum_name {
+            type Error = &'static str;
+
+            fn try_from(row: &$crate::jolt::instruction::RVTraceRow) -> Result<Self, Self::Error> {
+                $(
+                    if <$struct as $crate::jolt::instruction::JoltCustomInstruction>::matches(
+                        &row.instruction,
+                    ) {
+                        return Ok(<$struct as $crate::jolt::instruction::JoltCustomInstruction>::from_operands(
+                            row.register_state.rs1_val.unwrap(),
+                            row.register_state.rs2_val.unwrap(),
+                        )
+                        .into());
+                    }
+                )+
+                $crate::jolt::vm::rv32i_vm::RV32I::try_from(row).map(Self::from)
+            }
+        }
+    };
+}
+
+/// Generates a subtable enum extending `RV32ISubtables` with a list of LassoSubtable types,
+/// e.g.
+///
+/// ```ignore
+/// custom_subtable_enum!(MySubtables, ANDN: ANDNSubtable<F>);
+/// ```
+///
+/// to be used alongside an instruction set generated by `custom_instruction_set!`. `F` is
+/// the enum's field parameter, and each subtable must have a `new()` constructor. A VM over
+/// the two enums implements `Jolt` with the same `C` and `M` as `RV32IJoltVM`.
+#[macro_export]
+macro_rules! custom_subtable_enum {
+    ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
+        $crate::with_rv32i_subtables!(subtable_enum!($enum_name,), $($alias: $struct),+);
+    };
+}
+
+with_rv32i_instructions!(instruction_set!(RV32I,));
+with_rv32i_subtables!(subtable_enum!(RV32ISubtables,));
 
 // ==================== JOLT ====================
 
@@ -216,30 +566,47 @@ pub trait Serializable: CanonicalSerialize + CanonicalDeserialize + Sized {
 }
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct RV32IHyraxProof {
-    pub proof: RV32IJoltProof<Fr, PCS>,
-    pub commitments: JoltCommitments<PCS>,
+pub struct RV32IProof<CS: CommitmentScheme<Field = Fr>> {
+    pub proof: RV32IJoltProof<Fr, CS>,
+    pub commitments: JoltCommitments<CS>,
 }
 
-impl Serializable for RV32IHyraxProof {}
+impl<CS: CommitmentScheme<Field = Fr>> Serializable for RV32IProof<CS> {}
+
+pub type RV32IHyraxProof = RV32IProof<PCS>;
+pub type RV32IHyperKZGProof = RV32IProof<HyperKZG<Bn254>>;
+pub type RV32IZeromorphProof = RV32IProof<Zeromorph<Bn254>>;
+
+impl<F, CS> Serializable for JoltVerifierPreprocessing<F, CS>
+where
+    F: JoltField,
+    CS: CommitmentScheme<Field = F>,
+{
+}
 
 // ==================== TEST ====================
 
 #[cfg(test)]
 mod tests {
     use ark_bn254::{Bn254, Fr, G1Projective};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
 
     use std::collections::HashSet;
 
     use crate::field::JoltField;
     use crate::host;
     use crate::jolt::instruction::JoltInstruction;
-    use crate::jolt::vm::rv32i_vm::{Jolt, RV32IJoltVM, C, M};
+    use crate::jolt::vm::rv32i_vm::{Jolt, RV32IHyraxProof, RV32IJoltVM, Serializable, C, M, PCS};
+    use crate::jolt::vm::{
+        JoltPreprocessing, JoltVerifierPreprocessing, VERIFIER_PREPROCESSING_VERSION,
+    };
     use crate::poly::commitment::commitment_scheme::CommitmentScheme;
     use crate::poly::commitment::hyperkzg::HyperKZG;
     use crate::poly::commitment::hyrax::HyraxScheme;
     use crate::poly::commitment::mock::MockCommitScheme;
     use crate::poly::commitment::zeromorph::Zeromorph;
+    use sha3::{Digest, Keccak256};
+    use std::panic::AssertUnwindSafe;
     use std::sync::Mutex;
     use strum::{EnumCount, IntoEnumIterator};
 
@@ -277,8 +644,8 @@ mod tests {
         let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
         let mut program = host::Program::new("fibonacci-guest");
         program.set_input(&9u32);
-        let (bytecode, memory_init) = program.decode();
-        let (io_device, trace, circuit_flags) = program.trace();
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
         drop(artifact_guard);
 
         let preprocessing =
@@ -317,6 +684,92 @@ mod tests {
         fib_e2e::<Fr, HyperKZG<Bn254>>();
     }
 
+    fn fib_e2e_serialized_verifier_preprocessing<F: JoltField, PCS: CommitmentScheme<Field = F>>() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
+        drop(artifact_guard);
+
+        let preprocessing =
+            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let (proof, commitments) = <RV32IJoltVM as Jolt<F, PCS, C, M>>::prove(
+            io_device,
+            trace,
+            circuit_flags,
+            preprocessing.clone(),
+        );
+
+        let bytes = preprocessing
+            .verifier_preprocessing()
+            .serialize_to_bytes()
+            .unwrap();
+        let verifier_preprocessing =
+            JoltVerifierPreprocessing::<F, PCS>::deserialize_from_bytes(&bytes).unwrap();
+        let verification_result =
+            RV32IJoltVM::verify(verifier_preprocessing.into(), proof, commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn fib_e2e_serialized_verifier_preprocessing_hyrax() {
+        fib_e2e_serialized_verifier_preprocessing::<Fr, HyraxScheme<G1Projective>>();
+    }
+
+    #[test]
+    fn fib_e2e_serialized_verifier_preprocessing_hyperkzg() {
+        fib_e2e_serialized_verifier_preprocessing::<Fr, HyperKZG<Bn254>>();
+    }
+
+    #[test]
+    fn verifier_preprocessing_check_rejects_invalid_generators() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        let (bytecode, memory_init) = program.decode().unwrap();
+        drop(artifact_guard);
+
+        let preprocessing: JoltPreprocessing<Fr, HyraxScheme<G1Projective>> =
+            RV32IJoltVM::preprocess(bytecode, memory_init, 1 << 10, 1 << 10, 1 << 10);
+        let mut bytes = Vec::new();
+        preprocessing
+            .verifier_preprocessing()
+            .serialize_uncompressed(&mut bytes)
+            .unwrap();
+        let verifier_preprocessing =
+            JoltVerifierPreprocessing::<Fr, HyraxScheme<G1Projective>>::deserialize_uncompressed_unchecked(
+                &bytes[..],
+            )
+            .unwrap();
+        assert!(verifier_preprocessing.check().is_ok());
+
+        // Move the first generator off the curve: the version is followed by the length of
+        // the generators and then the x coordinate of the first one.
+        bytes[4 + 8] ^= 1;
+        let verifier_preprocessing =
+            JoltVerifierPreprocessing::<Fr, HyraxScheme<G1Projective>>::deserialize_uncompressed_unchecked(
+                &bytes[..],
+            )
+            .unwrap();
+        assert!(verifier_preprocessing.check().is_err());
+    }
+
+    #[test]
+    fn verifier_preprocessing_version_mismatch() {
+        let mut bytes = Vec::new();
+        (VERIFIER_PREPROCESSING_VERSION + 1)
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        assert!(
+            JoltVerifierPreprocessing::<Fr, MockCommitScheme<Fr>>::deserialize_from_bytes(&bytes)
+                .is_err()
+        );
+    }
+
     // TODO(sragss): Finish Binius.
     // #[test]
     // fn fib_e2e_binius() {
@@ -330,8 +783,44 @@ mod tests {
         program.set_input(&123u32);
         program.set_input(&234u32);
         program.set_input(&345u32);
-        let (bytecode, memory_init) = program.decode();
-        let (io_device, trace, circuit_flags) = program.trace();
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
+
+        let preprocessing =
+            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let (jolt_proof, jolt_commitments) =
+            <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::prove(
+                io_device,
+                trace,
+                circuit_flags,
+                preprocessing.clone(),
+            );
+
+        let verification_result = RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn sha2_chain_e2e_hyrax() {
+        let mut program = host::Program::new("sha2-chain-guest");
+        program.set_input(&[5u8; 32]);
+        program.set_input(&2u32);
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
+        // SHA-256(SHA-256([5; 32])), hashed with the SHA-256 compression precompile
+        let expected_output: [u8; 32] = [
+            0xbe, 0x32, 0x46, 0xb4, 0x6e, 0xb9, 0xc8, 0x31, 0xad, 0x5d, 0x18, 0x27, 0xc1, 0x15,
+            0xbe, 0x0c, 0x8f, 0xd6, 0x50, 0x2e, 0x81, 0x15, 0x6b, 0x69, 0x5a, 0x52, 0x2d, 0xf5,
+            0xa6, 0xe4, 0xe9, 0x9c,
+        ];
+        assert_eq!(
+            postcard::from_bytes::<[u8; 32]>(&io_device.outputs).unwrap(),
+            expected_output
+        );
 
         let preprocessing =
             RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
@@ -351,14 +840,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sha3_chain_e2e_hyrax() {
+        let mut program = host::Program::new("sha3-chain-guest");
+        program.set_input(&[5u8; 32]);
+        program.set_input(&2u32);
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
+        // Hashed with the Keccak-f precompile
+        let expected_output: [u8; 32] = Keccak256::digest([5u8; 32]).into();
+        assert_eq!(
+            postcard::from_bytes::<[u8; 32]>(&io_device.outputs).unwrap(),
+            expected_output
+        );
+
+        let preprocessing =
+            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let (jolt_proof, jolt_commitments) =
+            <RV32IJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::prove(
+                io_device,
+                trace,
+                circuit_flags,
+                preprocessing.clone(),
+            );
+
+        let verification_result = RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn print_e2e_hyrax() {
+        let mut program = host::Program::new("print-guest");
+        program.set_input(&3u32);
+        program.set_input(&4u32);
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, log) = program.trace().unwrap();
+        assert_eq!(log, b"3 + 4 = 7\n");
+        assert_eq!(postcard::from_bytes::<u32>(&io_device.outputs).unwrap(), 7);
+
+        let preprocessing =
+            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let (proof, commitments) = <RV32IJoltVM as Jolt<_, PCS, C, M>>::prove(
+            io_device,
+            trace,
+            circuit_flags,
+            preprocessing.clone(),
+        );
+        let proof = RV32IHyraxProof { proof, commitments }
+            .serialize_to_bytes()
+            .unwrap();
+
+        // The log is left out of the program IO the verifier checks...
+        let RV32IHyraxProof {
+            proof: jolt_proof,
+            commitments,
+        } = RV32IHyraxProof::deserialize_from_bytes(&proof).unwrap();
+        let verification_result =
+            RV32IJoltVM::verify(preprocessing.clone(), jolt_proof, commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+
+        // ...but the outputs next to it are still checked.
+        let RV32IHyraxProof {
+            proof: mut jolt_proof,
+            commitments,
+        } = RV32IHyraxProof::deserialize_from_bytes(&proof).unwrap();
+        jolt_proof.program_io.outputs[0] ^= 1;
+        // Some of the verifier's checks reject a proof by panicking rather than with an error
+        let verification_result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            RV32IJoltVM::verify(preprocessing, jolt_proof, commitments)
+        }));
+        assert!(!matches!(verification_result, Ok(Ok(()))));
+    }
+
     #[test]
     fn sha3_e2e_hyrax() {
         let _guard = SHA3_FILE_LOCK.lock().unwrap();
 
         let mut program = host::Program::new("sha3-guest");
         program.set_input(&[5u8; 32]);
-        let (bytecode, memory_init) = program.decode();
-        let (io_device, trace, circuit_flags) = program.trace();
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
 
         let preprocessing =
             RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
@@ -384,8 +953,8 @@ mod tests {
 
         let mut program = host::Program::new("sha3-guest");
         program.set_input(&[5u8; 32]);
-        let (bytecode, memory_init) = program.decode();
-        let (io_device, trace, circuit_flags) = program.trace();
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
 
         let preprocessing =
             RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
@@ -411,8 +980,8 @@ mod tests {
 
         let mut program = host::Program::new("sha3-guest");
         program.set_input(&[5u8; 32]);
-        let (bytecode, memory_init) = program.decode();
-        let (io_device, trace, circuit_flags) = program.trace();
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) = program.trace().unwrap();
 
         let preprocessing =
             RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
@@ -430,4 +999,207 @@ mod tests {
             verification_result.err()
         );
     }
+
+    mod custom {
+        use ark_bn254::Fr;
+        use ark_std::log2;
+        use rand::{prelude::StdRng, RngCore};
+        use serde::{Deserialize, Serialize};
+        use std::marker::PhantomData;
+
+        use super::{CommitmentScheme, Jolt, C, M};
+        use crate::field::JoltField;
+        use crate::jolt::instruction::{
+            JoltCustomInstruction, JoltInstruction, SubtableIndices, RV32IM,
+        };
+        use crate::jolt::subtable::LassoSubtable;
+        use crate::utils::instruction_utils::{
+            chunk_and_concatenate_operands, concatenate_lookups,
+        };
+        use crate::utils::split_bits;
+
+        #[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+        pub struct ANDNInstruction(pub u64, pub u64);
+
+        impl JoltInstruction for ANDNInstruction {
+            fn operands(&self) -> (u64, u64) {
+                (self.0, self.1)
+            }
+
+            fn combine_lookups<F: JoltField>(&self, vals: &[F], c: usize, m: usize) -> F {
+                concatenate_lookups(vals, c, log2(m) as usize / 2)
+            }
+
+            fn g_poly_degree(&self, _: usize) -> usize {
+                1
+            }
+
+            fn subtables<F: JoltField>(
+                &self,
+                c: usize,
+                _: usize,
+            ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+                vec![(Box::new(AndnSubtable::new()), SubtableIndices::from(0..c))]
+            }
+
+            fn to_indices(&self, c: usize, log_m: usize) -> Vec<usize> {
+                chunk_and_concatenate_operands(self.0, self.1, c, log_m)
+            }
+
+            fn lookup_entry(&self) -> u64 {
+                self.0 & !self.1
+            }
+
+            fn random(&self, rng: &mut StdRng) -> Self {
+                Self(rng.next_u32() as u64, rng.next_u32() as u64)
+            }
+        }
+
+        impl JoltCustomInstruction for ANDNInstruction {
+            const OPCODE: RV32IM = RV32IM::CUSTOM_0;
+            const FUNCT3: u32 = 1;
+            const FUNCT7: u32 = 0;
+
+            fn from_operands(x: u64, y: u64) -> Self {
+                Self(x, y)
+            }
+        }
+
+        #[derive(Default)]
+        pub struct AndnSubtable<F: JoltField> {
+            _field: PhantomData<F>,
+        }
+
+        impl<F: JoltField> AndnSubtable<F> {
+            pub fn new() -> Self {
+                Self {
+                    _field: PhantomData,
+                }
+            }
+        }
+
+        impl<F: JoltField> LassoSubtable<F> for AndnSubtable<F> {
+            fn materialize(&self, m: usize) -> Vec<F> {
+                let bits_per_operand = (log2(m) / 2) as usize;
+                (0..m)
+                    .map(|idx| {
+                        let (x, y) = split_bits(idx, bits_per_operand);
+                        F::from_u64((x & !y) as u64).unwrap()
+                    })
+                    .collect()
+            }
+
+            fn evaluate_mle(&self, point: &[F]) -> F {
+                let b = point.len() / 2;
+                let (x, y) = point.split_at(b);
+                let mut result = F::zero();
+                for i in 0..b {
+                    result +=
+                        F::from_u64(1u64 << i).unwrap() * x[b - i - 1] * (F::one() - y[b - i - 1]);
+                }
+                result
+            }
+        }
+
+        custom_instruction_set!(CustomInstructionSet, ANDN: ANDNInstruction);
+        custom_subtable_enum!(CustomSubtables, ANDN: AndnSubtable<F>);
+
+        pub enum CustomJoltVM {}
+
+        impl<F, PCS> Jolt<F, PCS, C, M> for CustomJoltVM
+        where
+            F: JoltField,
+            PCS: CommitmentScheme<Field = F>,
+        {
+            type InstructionSet = CustomInstructionSet;
+            type Subtables = CustomSubtables<F>;
+        }
+
+        crate::subtable_materialize_mle_parity_test!(
+            andn_materialize_mle_parity,
+            AndnSubtable<Fr>,
+            Fr,
+            1 << 16
+        );
+    }
+
+    #[test]
+    fn custom_instruction_set() {
+        use super::RV32I;
+        use crate::jolt::instruction::JoltInstructionSet;
+        use custom::{ANDNInstruction, CustomInstructionSet, CustomJoltVM};
+        use rand::{rngs::StdRng, SeedableRng};
+
+        assert_eq!(CustomInstructionSet::COUNT, RV32I::COUNT + 1);
+        let custom_instructions = CustomInstructionSet::custom_instructions();
+        assert_eq!(custom_instructions.len(), 1);
+        assert_eq!((custom_instructions[0].execute)(0b1100, 0b1010), 0b0100);
+
+        let mut rng = StdRng::seed_from_u64(1234567890);
+        for _ in 0..256 {
+            let instruction = ANDNInstruction::default().random(&mut rng);
+            crate::jolt_instruction_test!(instruction);
+        }
+        for _ in 0..256 {
+            let instruction = CustomInstructionSet::random_instruction(&mut rng);
+            crate::jolt_instruction_test!(instruction);
+
+            let bytes = bincode::serialize(&instruction).unwrap();
+            let deserialized: CustomInstructionSet = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(
+                CustomInstructionSet::enum_index(&deserialized),
+                CustomInstructionSet::enum_index(&instruction)
+            );
+            assert_eq!(deserialized.operands(), instruction.operands());
+        }
+
+        let mut subtable_set: HashSet<_> = HashSet::new();
+        for instruction in CustomInstructionSet::iter() {
+            for (subtable, _) in instruction.subtables::<Fr>(C, M) {
+                // panics if subtable cannot be cast to enum variant
+                let _ = <CustomJoltVM as Jolt<_, MockCommitScheme<Fr>, C, M>>::Subtables::from(
+                    subtable.subtable_id(),
+                );
+                subtable_set.insert(subtable.subtable_id());
+            }
+        }
+        assert_eq!(
+            subtable_set.len(),
+            <CustomJoltVM as Jolt<_, MockCommitScheme<Fr>, C, M>>::Subtables::COUNT,
+            "Unused enum variants in Subtables"
+        );
+    }
+
+    #[test]
+    fn custom_instruction_e2e_hyrax() {
+        use custom::{CustomInstructionSet, CustomJoltVM};
+
+        let mut program = host::Program::new("custom-instruction-guest");
+        program.set_input(&0b1100u32);
+        program.set_input(&0b1010u32);
+        let (bytecode, memory_init) = program.decode().unwrap();
+        let (io_device, trace, circuit_flags, _) =
+            program.trace::<Fr, CustomInstructionSet>().unwrap();
+        assert_eq!(
+            postcard::from_bytes::<u32>(&io_device.outputs).unwrap(),
+            0b0100
+        );
+
+        let preprocessing =
+            CustomJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let (jolt_proof, jolt_commitments) =
+            <CustomJoltVM as Jolt<_, HyraxScheme<G1Projective>, C, M>>::prove(
+                io_device,
+                trace,
+                circuit_flags,
+                preprocessing.clone(),
+            );
+
+        let verification_result = CustomJoltVM::verify(preprocessing, jolt_proof, jolt_commitments);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
 }
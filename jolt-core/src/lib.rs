@@ -24,3 +24,12 @@ pub mod poly;
 pub mod r1cs;
 pub mod subprotocols;
 pub mod utils;
+
+/// Dependencies of the code generated by the exported macros, so that the invoking crate
+/// does not need to depend on them.
+#[doc(hidden)]
+pub mod __private {
+    pub use rand;
+    pub use serde;
+    pub use strum;
+}
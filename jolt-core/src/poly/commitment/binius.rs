@@ -26,12 +26,13 @@ pub struct BiniusProof {}
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct BiniusBatchedProof {}
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct None {}
 
 impl CommitmentScheme for Binius128Scheme {
     type Field = crate::field::binius::BiniusField<binius_field::BinaryField128bPolyval>;
     type Setup = None;
+    type VerifierSetup = None;
     type Commitment = BiniusCommitment;
     type Proof = BiniusProof;
     type BatchedProof = BiniusBatchedProof;
@@ -39,6 +40,12 @@ impl CommitmentScheme for Binius128Scheme {
     fn setup(_shapes: &[CommitShape]) -> Self::Setup {
         None {}
     }
+    fn verifier_setup(_setup: &Self::Setup) -> Self::VerifierSetup {
+        None {}
+    }
+    fn from_verifier_setup(_verifier_setup: Self::VerifierSetup) -> Self::Setup {
+        None {}
+    }
     fn commit(_poly: &DensePolynomial<Self::Field>, _setup: &Self::Setup) -> Self::Commitment {
         todo!()
     }
@@ -52,6 +59,10 @@ impl CommitmentScheme for Binius128Scheme {
     fn commit_slice(_evals: &[Self::Field], _setup: &Self::Setup) -> Self::Commitment {
         todo!()
     }
+    fn commitment_size(_shape: &CommitShape) -> usize {
+        // `BiniusCommitment` is an empty placeholder, which serializes to no bytes.
+        0
+    }
     fn prove(
         _none: &Self::Setup,
         _poly: &DensePolynomial<Self::Field>,
@@ -35,11 +35,18 @@ pub enum BatchType {
 pub trait CommitmentScheme: Clone + Sync + Send + 'static {
     type Field: JoltField + Sized;
     type Setup: Clone + Sync + Send;
+    /// The part of `Setup` used by `verify` and `batch_verify`, which is
+    /// all a verifier needs to store. Depending on the scheme, this may be all of `Setup`.
+    type VerifierSetup: Clone + Sync + Send + CanonicalSerialize + CanonicalDeserialize;
     type Commitment: Sync + Send + CanonicalSerialize + CanonicalDeserialize + AppendToTranscript;
     type Proof: Sync + Send + CanonicalSerialize + CanonicalDeserialize;
     type BatchedProof: Sync + Send + CanonicalSerialize + CanonicalDeserialize;
 
     fn setup(shapes: &[CommitShape]) -> Self::Setup;
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup;
+    /// Reconstructs a `Setup` from its `VerifierSetup`. The result can be passed to
+    /// `verify` and `batch_verify`, but not used for committing or proving.
+    fn from_verifier_setup(verifier_setup: Self::VerifierSetup) -> Self::Setup;
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment;
     fn batch_commit(
         evals: &[&[Self::Field]],
@@ -47,6 +54,8 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
         batch_type: BatchType,
     ) -> Vec<Self::Commitment>;
     fn commit_slice(evals: &[Self::Field], setup: &Self::Setup) -> Self::Commitment;
+    /// Size in bytes of a (compressed) commitment to a polynomial of the given shape.
+    fn commitment_size(shape: &CommitShape) -> usize;
     fn batch_commit_polys(
         polys: &[DensePolynomial<Self::Field>],
         setup: &Self::Setup,
@@ -53,7 +53,7 @@ pub struct HyperKZGProverKey<P: Pairing> {
     pub kzg_pk: KZGProverKey<P>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct HyperKZGVerifierKey<P: Pairing> {
     pub kzg_vk: KZGVerifierKey<P>,
 }
@@ -510,6 +510,7 @@ where
 {
     type Field = P::ScalarField;
     type Setup = (HyperKZGProverKey<P>, HyperKZGVerifierKey<P>);
+    type VerifierSetup = HyperKZGVerifierKey<P>;
     type Commitment = HyperKZGCommitment<P>;
     type Proof = HyperKZGProof<P>;
     type BatchedProof = HyperKZGProof<P>;
@@ -524,6 +525,19 @@ where
         .trim(max_len)
     }
 
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        setup.1
+    }
+
+    fn from_verifier_setup(verifier_setup: Self::VerifierSetup) -> Self::Setup {
+        (
+            HyperKZGProverKey {
+                kzg_pk: KZGProverKey::empty(),
+            },
+            verifier_setup,
+        )
+    }
+
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
         assert!(
             setup.0.kzg_pk.g1_powers().len() > poly.Z.len(),
@@ -565,6 +579,10 @@ where
         )
     }
 
+    fn commitment_size(_shape: &CommitShape) -> usize {
+        HyperKZGCommitment::<P>(P::G1Affine::zero()).compressed_size()
+    }
+
     fn prove(
         setup: &Self::Setup,
         poly: &DensePolynomial<Self::Field>,
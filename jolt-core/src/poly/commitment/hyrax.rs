@@ -50,6 +50,7 @@ pub fn matrix_dimensions(num_vars: usize, ratio: usize) -> (usize, usize) {
 impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxScheme<G> {
     type Field = G::ScalarField;
     type Setup = PedersenGenerators<G>;
+    type VerifierSetup = PedersenGenerators<G>;
     type Commitment = HyraxCommitment<G>;
     type Proof = HyraxOpeningProof<G>;
     type BatchedProof = BatchedHyraxOpeningProof<G>;
@@ -68,6 +69,13 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxSch
         }
         PedersenGenerators::new(max_len, b"Jolt v1 Hyrax generators")
     }
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        // Opening proofs are checked with an MSM over the same generators
+        setup.clone()
+    }
+    fn from_verifier_setup(verifier_setup: Self::VerifierSetup) -> Self::Setup {
+        verifier_setup
+    }
     fn commit(poly: &DensePolynomial<Self::Field>, gens: &Self::Setup) -> Self::Commitment {
         HyraxCommitment::commit(poly, gens)
     }
@@ -81,6 +89,16 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxSch
     fn commit_slice(eval_slice: &[Self::Field], generators: &Self::Setup) -> Self::Commitment {
         HyraxCommitment::commit_slice(eval_slice, generators)
     }
+    fn commitment_size(shape: &CommitShape) -> usize {
+        let (num_rows, _) = matrix_dimensions(
+            shape.input_length.log_2(),
+            batch_type_to_ratio(&shape.batch_type),
+        );
+        HyraxCommitment {
+            row_commitments: vec![G::zero(); num_rows],
+        }
+        .compressed_size()
+    }
     fn prove(
         _setup: &Self::Setup,
         poly: &DensePolynomial<Self::Field>,
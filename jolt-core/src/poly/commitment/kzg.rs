@@ -5,6 +5,7 @@ use crate::utils::errors::ProofVerifyError;
 use ark_ec::scalar_mul::fixed_base::FixedBase;
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{One, UniformRand, Zero};
 use rand_core::{CryptoRng, RngCore};
 use std::marker::PhantomData;
@@ -95,12 +96,24 @@ impl<P: Pairing> KZGProverKey<P> {
         }
     }
 
+    /// A prover key without any powers, for setups that are only used for verification.
+    pub fn empty() -> Self {
+        Self {
+            srs: Arc::new(SRS {
+                g1_powers: vec![],
+                g2_powers: vec![],
+            }),
+            offset: 0,
+            supported_size: 0,
+        }
+    }
+
     pub fn g1_powers(&self) -> &[P::G1Affine] {
         &self.srs.g1_powers[self.offset..self.offset + self.supported_size]
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct KZGVerifierKey<P: Pairing> {
     pub g1: P::G1Affine,
     pub g2: P::G2Affine,
@@ -37,11 +37,14 @@ pub struct MockProof<F: JoltField> {
 impl<F: JoltField> CommitmentScheme for MockCommitScheme<F> {
     type Field = F;
     type Setup = ();
+    type VerifierSetup = ();
     type Commitment = MockCommitment<F>;
     type Proof = MockProof<F>;
     type BatchedProof = MockProof<F>;
 
     fn setup(_shapes: &[CommitShape]) -> Self::Setup {}
+    fn verifier_setup(_setup: &Self::Setup) -> Self::VerifierSetup {}
+    fn from_verifier_setup(_verifier_setup: Self::VerifierSetup) -> Self::Setup {}
     fn commit(poly: &DensePolynomial<Self::Field>, _setup: &Self::Setup) -> Self::Commitment {
         MockCommitment {
             poly: poly.to_owned(),
@@ -67,6 +70,12 @@ impl<F: JoltField> CommitmentScheme for MockCommitScheme<F> {
             poly: DensePolynomial::new(evals.to_owned()),
         }
     }
+    fn commitment_size(shape: &CommitShape) -> usize {
+        MockCommitment {
+            poly: DensePolynomial::new(vec![F::zero(); shape.input_length]),
+        }
+        .compressed_size()
+    }
     fn prove(
         _setup: &Self::Setup,
         _poly: &DensePolynomial<Self::Field>,
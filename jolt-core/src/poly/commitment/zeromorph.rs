@@ -58,7 +58,7 @@ pub struct ZeromorphProverKey<P: Pairing> {
     pub open_pp: KZGProverKey<P>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ZeromorphVerifierKey<P: Pairing> {
     pub kzg_vk: KZGVerifierKey<P>,
     pub tau_N_max_sub_2_N: P::G2Affine,
@@ -474,6 +474,7 @@ where
 {
     type Field = P::ScalarField;
     type Setup = (ZeromorphProverKey<P>, ZeromorphVerifierKey<P>);
+    type VerifierSetup = ZeromorphVerifierKey<P>;
     type Commitment = ZeromorphCommitment<P>;
     type Proof = ZeromorphProof<P>;
     type BatchedProof = ZeromorphProof<P>;
@@ -488,6 +489,20 @@ where
         .trim(max_len)
     }
 
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        setup.1
+    }
+
+    fn from_verifier_setup(verifier_setup: Self::VerifierSetup) -> Self::Setup {
+        (
+            ZeromorphProverKey {
+                commit_pp: KZGProverKey::empty(),
+                open_pp: KZGProverKey::empty(),
+            },
+            verifier_setup,
+        )
+    }
+
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
         assert!(
             setup.0.commit_pp.g1_powers().len() > poly.Z.len(),
@@ -531,6 +546,10 @@ where
         )
     }
 
+    fn commitment_size(_shape: &CommitShape) -> usize {
+        ZeromorphCommitment::<P>(P::G1Affine::zero()).compressed_size()
+    }
+
     fn prove(
         setup: &Self::Setup,
         poly: &DensePolynomial<Self::Field>,
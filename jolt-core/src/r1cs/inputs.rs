@@ -7,10 +7,7 @@
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::r1cs::jolt_constraints::JoltIn;
 use crate::utils::transcript::AppendToTranscript;
-use crate::{
-    jolt::vm::{rv32i_vm::RV32I, JoltCommitments},
-    utils::transcript::ProofTranscript,
-};
+use crate::{jolt::vm::JoltCommitments, utils::transcript::ProofTranscript};
 
 use super::key::UniformSpartanKey;
 use super::spartan::{SpartanError, UniformSpartanProof};
@@ -36,7 +33,7 @@ pub struct R1CSInputs<'a, F: JoltField> {
     pub chunks_query: Vec<F>,
     lookup_outputs: Vec<F>,
     pub circuit_flags_bits: Vec<F>,
-    instruction_flags_bits: Vec<F>,
+    pub instruction_flags_bits: Vec<F>,
 }
 
 impl<'a, F: JoltField> R1CSInputs<'a, F> {
@@ -176,6 +173,8 @@ pub struct R1CSCommitment<C: CommitmentScheme> {
     /// Operand chunks { x, y }
     pub chunks: Vec<C::Commitment>,
     pub circuit_flags: Vec<C::Commitment>,
+    /// Instruction flags beyond those of the instruction set, which are always zero
+    pub unused_instruction_flags: Vec<C::Commitment>,
 }
 
 impl<C: CommitmentScheme> AppendToTranscript for R1CSCommitment<C> {
@@ -193,6 +192,9 @@ impl<C: CommitmentScheme> AppendToTranscript for R1CSCommitment<C> {
         for commitment in &self.circuit_flags {
             commitment.append_to_transcript(transcript);
         }
+        for commitment in &self.unused_instruction_flags {
+            commitment.append_to_transcript(transcript);
+        }
         transcript.append_message(b"R1CSCommitment_end");
     }
 }
@@ -210,9 +212,11 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
         generators: &C::Setup,
         jolt_commitments: JoltCommitments<C>,
         C: usize,
+        num_instructions: usize,
         transcript: &mut ProofTranscript,
     ) -> Result<(), SpartanError> {
-        let witness_segment_commitments = Self::format_commitments(&jolt_commitments, C);
+        let witness_segment_commitments =
+            Self::format_commitments(&jolt_commitments, C, num_instructions);
         self.proof.verify_precommitted(
             &self.key,
             witness_segment_commitments,
@@ -225,6 +229,7 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
     pub fn format_commitments(
         jolt_commitments: &JoltCommitments<C>,
         C: usize,
+        num_instructions: usize,
     ) -> Vec<&C::Commitment> {
         let r1cs_commitments = &jolt_commitments.r1cs;
         let bytecode_trace_commitments = &jolt_commitments.bytecode.trace_commitments;
@@ -233,7 +238,7 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
         let instruction_lookup_indices_commitments =
             &jolt_commitments.instruction_lookups.trace_commitment[..C];
         let instruction_flag_commitments = &jolt_commitments.instruction_lookups.trace_commitment
-            [jolt_commitments.instruction_lookups.trace_commitment.len() - RV32I::COUNT - 1
+            [jolt_commitments.instruction_lookups.trace_commitment.len() - num_instructions - 1
                 ..jolt_commitments.instruction_lookups.trace_commitment.len() - 1];
 
         let mut combined_commitments: Vec<&C::Commitment> = Vec::new();
@@ -264,6 +269,13 @@ impl<F: JoltField, C: CommitmentScheme<Field = F>> R1CSProof<F, C> {
         combined_commitments.extend(r1cs_commitments.as_ref().unwrap().circuit_flags.iter());
 
         combined_commitments.extend(instruction_flag_commitments.iter());
+        combined_commitments.extend(
+            r1cs_commitments
+                .as_ref()
+                .unwrap()
+                .unused_instruction_flags
+                .iter(),
+        );
 
         combined_commitments.extend(r1cs_commitments.as_ref().unwrap().aux.iter());
 
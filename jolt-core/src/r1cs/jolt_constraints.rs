@@ -21,7 +21,7 @@ pub fn construct_jolt_constraints<F: JoltField>(
     let pc_constraint = OffsetEqConstraint::new(
         (JoltIn::Bytecode_ELFAddress, true),
         (Variable::Auxiliary(NEXT_PC), false),
-        (4 * JoltIn::Bytecode_ELFAddress + PC_START_ADDRESS, true),
+        (2 * JoltIn::Bytecode_ELFAddress + PC_START_ADDRESS, true),
     );
 
     // If the current instruction is virtual, check that the next instruction
@@ -118,6 +118,7 @@ pub enum JoltIn {
     OpFlags_IsVirtualInstruction,
     OpFlags_IsAssert,
     OpFlags_DoNotUpdatePC,
+    OpFlags_IsCompressed,
 
     // Instruction Flags
     // Should match JoltInstructionSet
@@ -150,16 +151,31 @@ pub enum JoltIn {
     IF_Virt_Assert_VALID_SIGNED_REMAINDER,
     IF_Virt_Assert_VALID_UNSIGNED_REMAINDER,
     IF_Virt_Assert_VALID_DIV0,
+    IF_Virt_Rotr,
+    // Reserved for the custom instructions of extended instruction sets
+    IF_Custom_0,
+    IF_Custom_1,
+    IF_Custom_2,
+    IF_Custom_3,
 }
 impl_r1cs_input_lc_conversions!(JoltIn);
 impl ConstraintInput for JoltIn {}
 
 pub const PC_START_ADDRESS: i64 = 0x80000000;
-const PC_NOOP_SHIFT: i64 = 4;
+// Bytecode addresses are compressed to 1 + (address - PC_START_ADDRESS) / 2, so the
+// R1CS PC of an instruction, 2 * ELFAddress + PC_START_ADDRESS, is its address plus 2.
+const PC_NOOP_SHIFT: i64 = 2;
 const LOG_M: usize = 16;
 const OPERAND_SIZE: usize = LOG_M / 2;
 pub const NEXT_PC: usize = 12;
 
+/// The number of instruction flags reserved for custom instructions, which follow the
+/// RV32I instruction flags. See `JoltCustomInstruction`.
+pub const NUM_CUSTOM_INSTRUCTION_FLAGS: usize = 4;
+/// The number of instruction flags in the R1CS inputs. Instruction sets with fewer
+/// instructions leave the remaining flags unset.
+pub const NUM_INSTRUCTION_FLAGS: usize = JoltIn::IF_Custom_3 as usize - JoltIn::IF_Add as usize + 1;
+
 pub struct UniformJoltConstraints {
     memory_start: u64,
 }
@@ -173,7 +189,7 @@ impl UniformJoltConstraints {
 impl<F: JoltField> R1CSConstraintBuilder<F> for UniformJoltConstraints {
     type Inputs = JoltIn;
     fn build_constraints(&self, cs: &mut R1CSBuilder<F, Self::Inputs>) {
-        let flags = input_range!(JoltIn::OpFlags_IsPC, JoltIn::IF_Virt_Assert_VALID_DIV0);
+        let flags = input_range!(JoltIn::OpFlags_IsPC, JoltIn::IF_Custom_3);
         for flag in flags {
             cs.constrain_binary(flag);
         }
@@ -182,7 +198,7 @@ impl<F: JoltField> R1CSConstraintBuilder<F> for UniformJoltConstraints {
 
         cs.constrain_pack_be(flags.to_vec(), JoltIn::Bytecode_Bitflags, 1);
 
-        let real_pc = 4i64 * JoltIn::Bytecode_ELFAddress + (PC_START_ADDRESS - PC_NOOP_SHIFT);
+        let real_pc = 2i64 * JoltIn::Bytecode_ELFAddress + (PC_START_ADDRESS - PC_NOOP_SHIFT);
         let x = cs.allocate_if_else(JoltIn::OpFlags_IsPC, real_pc, JoltIn::RS1_Read);
         let y = cs.allocate_if_else(
             JoltIn::OpFlags_IsImm,
@@ -273,7 +289,7 @@ impl<F: JoltField> R1CSConstraintBuilder<F> for UniformJoltConstraints {
         cs.constrain_eq_conditional(JoltIn::OpFlags_IsConcat, chunked_y, y);
 
         // if is_shift ? chunks_query[i] == zip(chunks_x[i], chunks_y[C-1]) : chunks_query[i] == zip(chunks_x[i], chunks_y[i])
-        let is_shift = JoltIn::IF_Sll + JoltIn::IF_Srl + JoltIn::IF_Sra;
+        let is_shift = JoltIn::IF_Sll + JoltIn::IF_Srl + JoltIn::IF_Sra + JoltIn::IF_Virt_Rotr;
         let chunks_x = input_range!(JoltIn::ChunksX_0, JoltIn::ChunksX_3);
         let chunks_y = input_range!(JoltIn::ChunksY_0, JoltIn::ChunksY_3);
         let chunks_query = input_range!(JoltIn::ChunksQ_0, JoltIn::ChunksQ_3);
@@ -288,7 +304,7 @@ impl<F: JoltField> R1CSConstraintBuilder<F> for UniformJoltConstraints {
         }
 
         // if (rd != 0 && update_rd_with_lookup_output == 1) constrain(rd_val == LookupOutput)
-        // if (rd != 0 && is_jump_instr == 1) constrain(rd_val == 4 * PC)
+        // if (rd != 0 && is_jump_instr == 1) constrain(rd_val == address of the next instruction)
         let rd_nonzero_and_lookup_to_rd =
             cs.allocate_prod(JoltIn::Bytecode_RD, JoltIn::OpFlags_LookupOutToRd);
         cs.constrain_eq_conditional(
@@ -297,21 +313,23 @@ impl<F: JoltField> R1CSConstraintBuilder<F> for UniformJoltConstraints {
             JoltIn::LookupOutput,
         );
         let rd_nonzero_and_jmp = cs.allocate_prod(JoltIn::Bytecode_RD, JoltIn::OpFlags_IsJmp);
-        let lhs = JoltIn::Bytecode_ELFAddress + (PC_START_ADDRESS - PC_NOOP_SHIFT);
+        let lhs = 2 * JoltIn::Bytecode_ELFAddress + (PC_START_ADDRESS - PC_NOOP_SHIFT + 4)
+            - 2 * JoltIn::OpFlags_IsCompressed;
         let rhs = JoltIn::RD_Write;
         cs.constrain_eq_conditional(rd_nonzero_and_jmp, lhs, rhs);
 
         let next_pc_jump = cs.allocate_if_else(
             JoltIn::OpFlags_IsJmp,
-            JoltIn::LookupOutput + 4,
-            4 * JoltIn::Bytecode_ELFAddress + PC_START_ADDRESS + 4
+            JoltIn::LookupOutput + PC_NOOP_SHIFT,
+            2 * JoltIn::Bytecode_ELFAddress + PC_START_ADDRESS + 4
+                - 2 * JoltIn::OpFlags_IsCompressed
                 - 4 * JoltIn::OpFlags_DoNotUpdatePC,
         );
 
         let should_branch = cs.allocate_prod(JoltIn::OpFlags_IsBranch, JoltIn::LookupOutput);
         let next_pc = cs.allocate_if_else(
             should_branch,
-            4 * JoltIn::Bytecode_ELFAddress + PC_START_ADDRESS + imm_signed,
+            2 * JoltIn::Bytecode_ELFAddress + PC_START_ADDRESS + imm_signed,
             next_pc_jump,
         );
 
@@ -332,9 +350,13 @@ mod tests {
     #[test]
     fn instruction_flags_length() {
         assert_eq!(
-            input_range!(JoltIn::IF_Add, JoltIn::IF_Virt_Assert_VALID_DIV0).len(),
+            input_range!(JoltIn::IF_Add, JoltIn::IF_Virt_Rotr).len(),
             RV32I::COUNT
         );
+        assert_eq!(
+            input_range!(JoltIn::IF_Custom_0, JoltIn::IF_Custom_3).len(),
+            NUM_CUSTOM_INSTRUCTION_FLAGS
+        );
     }
 
     #[test]
@@ -7,6 +7,8 @@ pub enum ProofVerifyError {
     InvalidInputLength(usize, usize),
     #[error("Input too large")]
     InputTooLarge,
+    #[error("Program did not terminate")]
+    ProgramNotTerminated,
     #[error("Proof verification failed")]
     #[default]
     InternalError,
@@ -4,7 +4,10 @@ extern crate proc_macro;
 
 use core::panic;
 
-use common::{attributes::parse_attributes, rv_trace::MemoryLayout};
+use common::{
+    attributes::{parse_attributes, CommitmentSchemeKind},
+    rv_trace::MemoryLayout,
+};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
@@ -62,6 +65,7 @@ impl MacroBuilder {
         let build_fn = self.make_build_fn();
         let execute_fn = self.make_execute_function();
         let analyze_fn = self.make_analyze_function();
+        let profile_fn = self.make_profile_function();
         let preprocess_fn = self.make_preprocess_func();
         let prove_fn = self.make_prove_func();
 
@@ -79,6 +83,7 @@ impl MacroBuilder {
             #build_fn
             #execute_fn
             #analyze_fn
+            #profile_fn
             #preprocess_fn
             #prove_fn
             #main_fn
@@ -87,6 +92,7 @@ impl MacroBuilder {
     }
 
     fn make_build_fn(&self) -> TokenStream2 {
+        let pcs = self.get_pcs_type();
         let fn_name = self.get_func_name();
         let build_fn_name = Ident::new(&format!("build_{}", fn_name), fn_name.span());
         let prove_output_ty = self.get_prove_output_type();
@@ -102,7 +108,7 @@ impl MacroBuilder {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #build_fn_name() -> (
                 impl Fn(#(#input_types),*) -> #prove_output_ty + Sync + Send,
-                impl Fn(jolt::RV32IHyraxProof) -> bool + Sync + Send
+                impl Fn(jolt::RV32IProof<#pcs>) -> bool + Sync + Send
             ) {
                 #imports
                 let (program, preprocessing) = #preprocess_fn_name();
@@ -119,7 +125,7 @@ impl MacroBuilder {
                 };
 
 
-                let verify_closure = move |proof: jolt::RV32IHyraxProof| {
+                let verify_closure = move |proof: jolt::RV32IProof<#pcs>| {
                     let program = (*program_cp).clone();
                     let preprocessing = (*preprocessing_cp).clone();
                     RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments).is_ok()
@@ -172,16 +178,57 @@ impl MacroBuilder {
                 #set_mem_size
                 #(#set_program_args;)*
 
-                program.trace_analyze::<jolt::F>()
+                program
+                    .trace_analyze::<jolt::F>()
+                    .unwrap_or_else(|err| panic!("failed to trace guest: {}", err))
+             }
+        }
+    }
+
+    fn make_profile_function(&self) -> TokenStream2 {
+        let set_mem_size = self.make_set_linker_parameters();
+        let guest_name = self.get_guest_name();
+        let imports = self.make_imports();
+        let set_std = self.make_set_std();
+
+        let fn_name = self.get_func_name();
+        let fn_name_str = fn_name.to_string();
+        let profile_fn_name = Ident::new(&format!("profile_{}", fn_name), fn_name.span());
+        let inputs = &self.func.sig.inputs;
+        let set_program_args = self.func_args.iter().map(|(name, _)| {
+            quote! {
+                program.set_input(&#name);
+            }
+        });
+
+        quote! {
+             #[cfg(not(target_arch = "wasm32"))]
+             #[cfg(not(feature = "guest"))]
+             pub fn #profile_fn_name(#inputs) -> jolt::host::profile::GuestProfile {
+                #imports
+
+                let mut program = Program::new(#guest_name);
+                program.set_func(#fn_name_str);
+                #set_std
+                #set_mem_size
+                #(#set_program_args;)*
+
+                program
+                    .profile()
+                    .unwrap_or_else(|err| panic!("failed to trace guest: {}", err))
              }
         }
     }
 
     fn make_preprocess_func(&self) -> TokenStream2 {
+        let pcs = self.get_pcs_type();
         let set_mem_size = self.make_set_linker_parameters();
         let guest_name = self.get_guest_name();
         let imports = self.make_imports();
         let set_std = self.make_set_std();
+        let attributes = parse_attributes(&self.attr);
+        let max_bytecode_size = attributes.max_bytecode_size as usize;
+        let max_trace_length = attributes.max_trace_length as usize;
 
         let fn_name = self.get_func_name();
         let fn_name_str = fn_name.to_string();
@@ -190,7 +237,7 @@ impl MacroBuilder {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #preprocess_fn_name() -> (
                 jolt::host::Program,
-                jolt::JoltPreprocessing<jolt::F, jolt::PCS>
+                jolt::JoltPreprocessing<jolt::F, #pcs>
             ) {
                 #imports
 
@@ -198,16 +245,17 @@ impl MacroBuilder {
                 program.set_func(#fn_name_str);
                 #set_std
                 #set_mem_size
-                let (bytecode, memory_init) = program.decode();
+                let (bytecode, memory_init) = program
+                    .decode()
+                    .unwrap_or_else(|err| panic!("failed to decode guest: {}", err));
 
-                // TODO(moodlezoup): Feed in size parameters via macro
-                let preprocessing: JoltPreprocessing<jolt::F, jolt::PCS> =
+                let preprocessing: JoltPreprocessing<jolt::F, #pcs> =
                     RV32IJoltVM::preprocess(
                         bytecode,
                         memory_init,
+                        #max_bytecode_size,
                         1 << 20,
-                        1 << 20,
-                        1 << 24
+                        #max_trace_length
                     );
 
                 (program, preprocessing)
@@ -216,6 +264,7 @@ impl MacroBuilder {
     }
 
     fn make_prove_func(&self) -> TokenStream2 {
+        let pcs = self.get_pcs_type();
         let prove_output_ty = self.get_prove_output_type();
 
         let handle_return = match &self.func.sig.output {
@@ -242,15 +291,20 @@ impl MacroBuilder {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #prove_fn_name(
                 mut program: jolt::host::Program,
-                preprocessing: jolt::JoltPreprocessing<jolt::F, jolt::PCS>,
+                preprocessing: jolt::JoltPreprocessing<jolt::F, #pcs>,
                 #inputs
             ) -> #prove_output_ty {
                 #imports
 
                 #(#set_program_args;)*
 
-                let (io_device, trace, circuit_flags) =
-                    program.trace();
+                let (io_device, trace, circuit_flags, _) = program
+                    .trace()
+                    .unwrap_or_else(|err| panic!("failed to trace guest: {}", err));
+
+                if let Some(guest_panic) = io_device.guest_panic() {
+                    return Err(guest_panic);
+                }
 
                 let output_bytes = io_device.outputs.clone();
 
@@ -263,12 +317,12 @@ impl MacroBuilder {
 
                 #handle_return
 
-                let proof = jolt::RV32IHyraxProof {
+                let proof = jolt::RV32IProof {
                     proof: jolt_proof,
                     commitments: jolt_commitments,
                 };
 
-                (ret_val, proof)
+                Ok((ret_val, proof))
             }
         }
     }
@@ -315,7 +369,9 @@ impl MacroBuilder {
             },
         };
 
-        let panic_fn = self.make_panic(memory_layout.panic);
+        let termination = memory_layout.termination;
+        let panic_fn = self.make_panic(&memory_layout);
+        let set_panic_hook = self.make_set_panic_hook(&memory_layout);
         let declare_alloc = self.make_allocator();
 
         quote! {
@@ -337,26 +393,39 @@ impl MacroBuilder {
             #[cfg(feature = "guest")]
             #[no_mangle]
             pub extern "C" fn main() {
+                #set_panic_hook
                 let mut offset = 0;
                 #get_input_slice
                 #(#args_fetch;)*
                 #check_input_len
                 #block
                 #handle_return
+
+                unsafe {
+                    core::ptr::write_volatile(#termination as *mut u8, 1);
+                }
             }
 
             #panic_fn
         }
     }
 
-    fn make_panic(&self, panic_address: u64) -> TokenStream2 {
+    fn make_panic(&self, memory_layout: &MemoryLayout) -> TokenStream2 {
+        let panic_address = memory_layout.panic;
+        let termination_address = memory_layout.termination;
+        let panic_info_start = memory_layout.panic_info_start;
+        let panic_info_len = memory_layout.panic_info_end - memory_layout.panic_info_start;
+
         if self.std {
+            // The panic info is written by the hook installed in `main`, before the
+            // runtime calls `jolt_panic`.
             quote! {
                 #[cfg(feature = "guest")]
                 #[no_mangle]
                 pub extern "C" fn jolt_panic() {
                     unsafe {
                         core::ptr::write_volatile(#panic_address as *mut u8, 1);
+                        core::ptr::write_volatile(#termination_address as *mut u8, 1);
                     }
 
                     loop {}
@@ -369,9 +438,16 @@ impl MacroBuilder {
 
                 #[cfg(feature = "guest")]
                 #[panic_handler]
-                fn panic(_info: &PanicInfo) -> ! {
+                fn panic(info: &PanicInfo) -> ! {
                     unsafe {
+                        jolt::write_panic_info(
+                            #panic_info_start as usize,
+                            #panic_info_len as usize,
+                            info.location(),
+                            info.message(),
+                        );
                         core::ptr::write_volatile(#panic_address as *mut u8, 1);
+                        core::ptr::write_volatile(#termination_address as *mut u8, 1);
                     }
 
                     loop {}
@@ -380,6 +456,33 @@ impl MacroBuilder {
         }
     }
 
+    fn make_set_panic_hook(&self, memory_layout: &MemoryLayout) -> TokenStream2 {
+        if !self.std {
+            return quote! {};
+        }
+
+        let panic_info_start = memory_layout.panic_info_start;
+        let panic_info_len = memory_layout.panic_info_end - memory_layout.panic_info_start;
+        quote! {
+            std::panic::set_hook(Box::new(|info| {
+                let payload = info.payload();
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or_default();
+                unsafe {
+                    jolt::write_panic_info(
+                        #panic_info_start as usize,
+                        #panic_info_len as usize,
+                        info.location(),
+                        message,
+                    );
+                }
+            }));
+        }
+    }
+
     fn make_allocator(&self) -> TokenStream2 {
         if self.std {
             quote! {}
@@ -419,27 +522,113 @@ impl MacroBuilder {
             use wasm_bindgen::prelude::*;
             #[cfg(target_arch = "wasm32")]
             use std::vec::Vec;
-            #[cfg(target_arch = "wasm32")]
-            use rmp_serde::Deserializer;
-            #[cfg(target_arch = "wasm32")]
-            use serde::{Deserialize, Serialize};
 
+            /// Reason a proof was rejected by one of the generated `verify_*` functions.
             #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
-            use jolt::host::ELFInstruction;
+            #[wasm_bindgen]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub enum VerifyErrorKind {
+                PreprocessingDecode,
+                ProofDecode,
+                OutputDecode,
+                InputMismatch,
+                OutputMismatch,
+                GuestPanic,
+                InvalidInputLength,
+                InputTooLarge,
+                ProgramNotTerminated,
+                InternalError,
+                DecompressionError,
+                SpartanError,
+                KeyLengthError,
+            }
 
+            /// Error thrown to JavaScript when verification fails.
             #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
-            #[derive(Serialize, Deserialize)]
-            struct DecodedData {
-                bytecode: Vec<ELFInstruction>,
-                memory_init: Vec<(u64, u8)>,
+            #[wasm_bindgen(getter_with_clone)]
+            #[derive(Clone, Debug)]
+            pub struct VerifyError {
+                pub kind: VerifyErrorKind,
+                pub message: String,
             }
 
-            #[cfg(target_arch = "wasm32")]
-            fn deserialize_from_bin<'a, T: Deserialize<'a>>(
-                data: &'a [u8],
-            ) -> Result<T, rmp_serde::decode::Error> {
-                let mut de = Deserializer::new(data);
-                Deserialize::deserialize(&mut de)
+            #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
+            #[wasm_bindgen]
+            impl VerifyError {
+                #[wasm_bindgen(js_name = toString)]
+                pub fn to_js_string(&self) -> String {
+                    format!("{:?}: {}", self.kind, self.message)
+                }
+            }
+
+            #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
+            impl VerifyError {
+                fn new(kind: VerifyErrorKind, message: impl ToString) -> Self {
+                    Self {
+                        kind,
+                        message: message.to_string(),
+                    }
+                }
+            }
+
+            #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
+            impl From<jolt::ProofVerifyError> for VerifyError {
+                fn from(err: jolt::ProofVerifyError) -> Self {
+                    let kind = match err {
+                        jolt::ProofVerifyError::InvalidInputLength(..) => VerifyErrorKind::InvalidInputLength,
+                        jolt::ProofVerifyError::InputTooLarge => VerifyErrorKind::InputTooLarge,
+                        jolt::ProofVerifyError::ProgramNotTerminated => VerifyErrorKind::ProgramNotTerminated,
+                        jolt::ProofVerifyError::InternalError => VerifyErrorKind::InternalError,
+                        jolt::ProofVerifyError::DecompressionError(..) => VerifyErrorKind::DecompressionError,
+                        jolt::ProofVerifyError::SpartanError(..) => VerifyErrorKind::SpartanError,
+                        jolt::ProofVerifyError::KeyLengthError(..) => VerifyErrorKind::KeyLengthError,
+                    };
+                    Self::new(kind, err)
+                }
+            }
+
+            /// Verifies `proof_bytes` and returns the proven program IO, provided that the
+            /// proof was generated for `inputs` and, if given, `outputs`. Both are expected
+            /// to be postcard-encoded, exactly as the host writes them into the `JoltDevice`.
+            #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
+            fn verify_program_io<PCS: jolt::CommitmentScheme<Field = jolt::F>>(
+                preprocessing_data: &[u8],
+                proof_bytes: &[u8],
+                inputs: &[u8],
+                outputs: Option<&[u8]>,
+            ) -> Result<jolt::tracer::JoltDevice, VerifyError> {
+                use jolt::{Jolt, JoltVerifierPreprocessing, RV32IJoltVM, RV32IProof, Serializable};
+
+                let preprocessing =
+                    JoltVerifierPreprocessing::<jolt::F, PCS>::deserialize_from_bytes(preprocessing_data)
+                        .map_err(|err| VerifyError::new(VerifyErrorKind::PreprocessingDecode, err))?;
+                let proof = RV32IProof::<PCS>::deserialize_from_bytes(proof_bytes)
+                    .map_err(|err| VerifyError::new(VerifyErrorKind::ProofDecode, err))?;
+
+                // A valid proof only attests to the program IO it carries, so it must
+                // match what the caller expects before it says anything about them.
+                let program_io = proof.proof.program_io.clone();
+                if let Some(guest_panic) = program_io.guest_panic() {
+                    return Err(VerifyError::new(
+                        VerifyErrorKind::GuestPanic,
+                        format!("proof attests to a guest panic: {}", guest_panic),
+                    ));
+                }
+                if program_io.inputs != inputs {
+                    return Err(VerifyError::new(
+                        VerifyErrorKind::InputMismatch,
+                        "proof was generated for different inputs",
+                    ));
+                }
+                if matches!(outputs, Some(outputs) if program_io.outputs != outputs) {
+                    return Err(VerifyError::new(
+                        VerifyErrorKind::OutputMismatch,
+                        "proof attests to different outputs",
+                    ));
+                }
+
+                RV32IJoltVM::verify(preprocessing.into(), proof.proof, proof.commitments)?;
+                Ok(program_io)
             }
         }
     }
@@ -468,6 +657,11 @@ impl MacroBuilder {
             program.set_max_output_size(#value);
         });
 
+        let value = attributes.max_cycles;
+        code.push(quote! {
+            program.set_max_cycles(#value);
+        });
+
         quote! {
             #(#code;)*
         }
@@ -486,16 +680,25 @@ impl MacroBuilder {
     }
 
     fn get_prove_output_type(&self) -> TokenStream2 {
+        let pcs = self.get_pcs_type();
         match &self.func.sig.output {
             ReturnType::Default => quote! {
-                ((), jolt::RV32IHyraxProof)
+                Result<((), jolt::RV32IProof<#pcs>), jolt::GuestPanic>
             },
             ReturnType::Type(_, ty) => quote! {
-                (#ty, jolt::RV32IHyraxProof)
+                Result<(#ty, jolt::RV32IProof<#pcs>), jolt::GuestPanic>
             },
         }
     }
 
+    fn get_pcs_type(&self) -> TokenStream2 {
+        match parse_attributes(&self.attr).pcs {
+            CommitmentSchemeKind::Hyrax => quote! { jolt::HyraxScheme<jolt::G> },
+            CommitmentSchemeKind::HyperKZG => quote! { jolt::HyperKZG<jolt::Bn254> },
+            CommitmentSchemeKind::Zeromorph => quote! { jolt::Zeromorph<jolt::Bn254> },
+        }
+    }
+
     fn get_func_args(func: &ItemFn) -> Vec<(Ident, Box<Type>)> {
         let mut args = Vec::new();
         for arg in &func.sig.inputs {
@@ -530,28 +733,42 @@ impl MacroBuilder {
     }
 
     fn make_wasm_function(&self) -> TokenStream2 {
+        let pcs = self.get_pcs_type();
         let fn_name = self.get_func_name();
         let verify_wasm_fn_name = Ident::new(&format!("verify_{}", fn_name), fn_name.span());
+        let verify_with_output_wasm_fn_name =
+            Ident::new(&format!("verify_{}_with_output", fn_name), fn_name.span());
+
+        let output_ty = match &self.func.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => quote! { #ty },
+        };
 
         quote! {
             #[wasm_bindgen]
             #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
-            pub fn #verify_wasm_fn_name(preprocessing_data: &[u8], proof_bytes: &[u8]) -> bool {
-                use jolt::{Jolt, RV32IHyraxProof, RV32IJoltVM};
-
-                let decoded_preprocessing_data: DecodedData = deserialize_from_bin(preprocessing_data).unwrap();
-                let proof = RV32IHyraxProof::deserialize_from_bytes(proof_bytes).unwrap();
-
-                let preprocessing = RV32IJoltVM::preprocess(
-                    decoded_preprocessing_data.bytecode,
-                    decoded_preprocessing_data.memory_init,
-                    1 << 20,
-                    1 << 20,
-                    1 << 24,
-                );
+            pub fn #verify_wasm_fn_name(
+                preprocessing_data: &[u8],
+                proof_bytes: &[u8],
+                inputs: &[u8],
+                outputs: &[u8],
+            ) -> Result<(), JsValue> {
+                verify_program_io::<#pcs>(preprocessing_data, proof_bytes, inputs, Some(outputs))?;
+                Ok(())
+            }
 
-                let result = RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments);
-                result.is_ok()
+            #[wasm_bindgen]
+            #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
+            pub fn #verify_with_output_wasm_fn_name(
+                preprocessing_data: &[u8],
+                proof_bytes: &[u8],
+                inputs: &[u8],
+            ) -> Result<JsValue, JsValue> {
+                let program_io = verify_program_io::<#pcs>(preprocessing_data, proof_bytes, inputs, None)?;
+                let output = jolt::postcard::from_bytes::<#output_ty>(&program_io.outputs)
+                    .map_err(|err| VerifyError::new(VerifyErrorKind::OutputDecode, err))?;
+                serde_wasm_bindgen::to_value(&output)
+                    .map_err(|err| VerifyError::new(VerifyErrorKind::OutputDecode, err).into())
             }
         }
     }
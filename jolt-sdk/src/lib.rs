@@ -12,3 +12,12 @@ pub use host_utils::*;
 
 pub mod alloc;
 pub use alloc::*;
+
+pub mod panic;
+pub use panic::write_panic_info;
+
+pub mod precompiles;
+pub use precompiles::*;
+
+#[doc(hidden)]
+pub mod print;
@@ -0,0 +1,61 @@
+use core::fmt::{self, Display, Write};
+use core::panic::Location;
+
+/// The line, column, file length and message length, as little-endian `u32`s.
+const HEADER_SIZE: usize = 16;
+
+/// Writes the location and message of a panic into the panic info region of the guest's
+/// memory layout, from which the host decodes them as a `GuestPanic`. The file and the
+/// message are truncated to fit the region.
+///
+/// # Safety
+///
+/// `address..address + len` must be the panic info region of the memory layout the guest
+/// was built with, and `len` must be at least 16.
+pub unsafe fn write_panic_info(
+    address: usize,
+    len: usize,
+    location: Option<&Location>,
+    message: impl Display,
+) {
+    let region = core::slice::from_raw_parts_mut(address as *mut u8, len);
+    let (header, body) = region.split_at_mut(HEADER_SIZE);
+    let mut writer = TruncatingWriter {
+        buffer: body,
+        len: 0,
+    };
+
+    let (line, column) = match location {
+        Some(location) => {
+            let _ = writer.write_str(location.file());
+            (location.line(), location.column())
+        }
+        None => (0, 0),
+    };
+    let file_len = writer.len;
+    let _ = write!(writer, "{}", message);
+    let message_len = writer.len - file_len;
+
+    let fields = [line, column, file_len as u32, message_len as u32];
+    for (field, value) in header.chunks_exact_mut(4).zip(fields) {
+        field.copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+struct TruncatingWriter<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for TruncatingWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let available = self.buffer.len() - self.len;
+        let n = s.len().min(available);
+        self.buffer[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        if n < s.len() {
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+//! Precompiles, which the guest invokes through dedicated instructions on the custom-2
+//! opcode. Jolt proves each of them with a virtual sequence that is far shorter than the
+//! RV32 instructions computing the same function. When the guest function runs natively on
+//! the host, they are computed in software instead, by the implementation the tracer executes
+//! them with.
+
+/// The SHA-256 initial hash value.
+pub const SHA256_INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The SHA-256 digest of `message`, of any length. The message is padded and split into
+/// blocks here, each of which takes one call to `sha256_compress`.
+pub fn sha256(message: &[u8]) -> [u8; 32] {
+    let mut state = SHA256_INITIAL_STATE;
+    let mut blocks = message.chunks_exact(64);
+    for block in &mut blocks {
+        sha256_compress(&mut state, &sha256_block(block));
+    }
+
+    // The padding: a one bit after the message, then zeros up to the message length in bits,
+    // which fill one more block, or two if the last eight bytes of the first are taken.
+    let remainder = blocks.remainder();
+    let mut padding = [0u8; 128];
+    padding[..remainder.len()].copy_from_slice(remainder);
+    padding[remainder.len()] = 0x80;
+    let padding_len = if remainder.len() < 56 { 64 } else { 128 };
+    let bit_len = message.len() as u64 * 8;
+    padding[padding_len - 8..padding_len].copy_from_slice(&bit_len.to_be_bytes());
+    for block in padding[..padding_len].chunks_exact(64) {
+        sha256_compress(&mut state, &sha256_block(block));
+    }
+
+    let mut digest = [0u8; 32];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// The 16 big-endian words of a 64-byte block.
+fn sha256_block(bytes: &[u8]) -> [u32; 16] {
+    core::array::from_fn(|i| u32::from_be_bytes(bytes[4 * i..4 * i + 4].try_into().unwrap()))
+}
+
+/// The SHA-256 compression function, applied to `state` with a block of 16 message words.
+/// The words are big-endian, i.e. `block[0]` holds the first four bytes of the block with
+/// the first byte in its most significant bits. See `sha256` for hashing a whole message.
+pub fn sha256_compress(state: &mut [u32; 8], block: &[u32; 16]) {
+    #[cfg(target_arch = "riscv32")]
+    unsafe {
+        core::arch::asm!(
+            ".insn r 0x5b, 0x0, 0x0, x0, {rs1}, {rs2}",
+            rs1 = in(reg) state.as_mut_ptr(),
+            rs2 = in(reg) block.as_ptr(),
+            options(nostack),
+        );
+    }
+    #[cfg(not(target_arch = "riscv32"))]
+    common::precompiles::sha256_compress(state, block);
+}
+
+/// The Keccak-f[1600] permutation, applied to the 25 lanes of `state`, where lane (x, y) is
+/// `state[x + 5 * y]`. See `keccak256` for hashing a whole message.
+pub fn keccak_f(state: &mut [u64; 25]) {
+    #[cfg(target_arch = "riscv32")]
+    unsafe {
+        core::arch::asm!(
+            ".insn r 0x5b, 0x1, 0x0, x0, {rs1}, x0",
+            rs1 = in(reg) state.as_mut_ptr(),
+            options(nostack),
+        );
+    }
+    #[cfg(not(target_arch = "riscv32"))]
+    common::precompiles::keccak_f(state);
+}
+
+/// The Keccak-256 digest of `message`, of any length, as used by Ethereum. The message is
+/// padded and absorbed into the state a block of the 136-byte rate at a time, each of which
+/// takes one call to `keccak_f`.
+pub fn keccak256(message: &[u8]) -> [u8; 32] {
+    let mut state = [0u64; 25];
+    let mut blocks = message.chunks_exact(KECCAK256_RATE);
+    for block in &mut blocks {
+        keccak256_absorb(&mut state, block);
+    }
+
+    // The padding: a one bit after the message and a one bit at the end of the block, in the
+    // same byte if only one is left.
+    let remainder = blocks.remainder();
+    let mut block = [0u8; KECCAK256_RATE];
+    block[..remainder.len()].copy_from_slice(remainder);
+    block[remainder.len()] ^= 0x01;
+    block[KECCAK256_RATE - 1] ^= 0x80;
+    keccak256_absorb(&mut state, &block);
+
+    let mut digest = [0u8; 32];
+    for (bytes, lane) in digest.chunks_exact_mut(8).zip(state) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+    digest
+}
+
+/// The number of bytes of the state a Keccak-256 block is absorbed into.
+const KECCAK256_RATE: usize = 136;
+
+/// XORs a block into the little-endian lanes of `state` and permutes it.
+fn keccak256_absorb(state: &mut [u64; 25], block: &[u8]) {
+    for (lane, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+        *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    keccak_f(state);
+}
@@ -0,0 +1,58 @@
+use core::fmt::{self, Write};
+
+/// Bytes stored to this address are captured by the host as the guest's log. It is the byte
+/// below the start of RAM, `common::constants::LOG_ADDRESS`, which guests can't import as
+/// `common` is not `no_std`.
+const LOG_ADDRESS: usize = 0x8000_0000 - 1;
+
+// Checked against the memory layout in every build that has `common`, i.e. every host
+// build, so that the two addresses can't drift apart.
+#[cfg(not(target_arch = "riscv32"))]
+const _: () = assert!(LOG_ADDRESS as u64 == common::constants::LOG_ADDRESS);
+
+/// Prints to the log of the execution, which the host gets from `Program::trace`. The log
+/// is not part of the program IO, so a proof of the execution does not disclose it. When
+/// the guest function runs natively on the host, prints to stdout instead.
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::print::_print(format_args!($($arg)*))
+    };
+}
+
+/// Like [`print!`], but appends a newline.
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::print!("\n")
+    };
+    ($($arg:tt)*) => {{
+        $crate::print::_print(format_args!($($arg)*));
+        $crate::print!("\n");
+    }};
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    let _ = LogWriter.write_fmt(args);
+}
+
+struct LogWriter;
+
+impl Write for LogWriter {
+    #[cfg(not(feature = "host"))]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            unsafe {
+                core::ptr::write_volatile(LOG_ADDRESS as *mut u8, byte);
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "host")]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        std::print!("{}", s);
+        Ok(())
+    }
+}
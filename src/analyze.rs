@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use ark_bn254::{Bn254, G1Projective};
+use eyre::Result;
+use jolt_core::host::analyze::CostEstimate;
+use jolt_core::poly::commitment::{hyperkzg::HyperKZG, hyrax::HyraxScheme, zeromorph::Zeromorph};
+
+use crate::build_wasm::is_std;
+use crate::provable::find_provable_function;
+use crate::verify::parse_bytes_arg;
+
+pub fn analyze(func: &str, input: Option<String>) {
+    match estimate_cost(func, input.as_deref()) {
+        Ok(estimate) => print_estimate(func, &estimate),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn estimate_cost(func: &str, input: Option<&str>) -> Result<CostEstimate> {
+    let function = find_provable_function(Path::new("guest"), func)?;
+    let input = parse_bytes_arg(input, "input")?.unwrap_or_default();
+
+    let mut program = function.program(is_std()?);
+    program.set_input_bytes(&input);
+    Ok(program.estimate_cost()?)
+}
+
+fn print_estimate(func: &str, estimate: &CostEstimate) {
+    println!("Function:             {}", func);
+    println!(
+        "Trace length:         {} (padded to {})",
+        estimate.trace_length, estimate.padded_trace_length
+    );
+    println!("Bytecode size:        {}", estimate.bytecode_size);
+    println!("Max address touched:  {:#x}", estimate.max_address);
+    println!("Memory size:          {}", estimate.memory_size);
+
+    println!();
+    println!("Subtable lookups:     {}", estimate.num_lookups());
+    for (subtable, count) in &estimate.subtable_lookups {
+        println!("  {:<20} {}", subtable, count);
+    }
+
+    println!();
+    println!("Commitment size per polynomial, in bytes:");
+    println!(
+        "  {:<28} {:>10} {:>10} {:>10}",
+        "shape", "hyrax", "hyperkzg", "zeromorph"
+    );
+    let sizes = [
+        estimate.commitment_sizes::<HyraxScheme<G1Projective>>(),
+        estimate.commitment_sizes::<HyperKZG<Bn254>>(),
+        estimate.commitment_sizes::<Zeromorph<Bn254>>(),
+    ];
+    for (i, shape) in estimate.commitment_shapes.iter().enumerate() {
+        let shape = format!("{} ({:?})", shape.input_length, shape.batch_type);
+        println!(
+            "  {:<28} {:>10} {:>10} {:>10}",
+            shape, sizes[0][i], sizes[1][i], sizes[2][i]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use common::attributes::{parse_attributes, Attributes};
+use common::attributes::CommitmentSchemeKind;
 
 use std::{
     fs::{self, File},
@@ -6,88 +6,38 @@ use std::{
     path::Path,
 };
 
-use eyre::Result;
-use jolt_core::host::{ELFInstruction, Program};
-use rmp_serde::Serializer;
-use serde::{Deserialize, Serialize};
-use syn::{Attribute, ItemFn, Meta, PathSegment};
+use ark_bn254::{Bn254, G1Projective};
+use clap::ValueEnum;
+use eyre::{eyre, Result};
+use jolt_core::jolt::vm::rv32i_vm::Serializable;
+use jolt_core::poly::commitment::{hyperkzg::HyperKZG, hyrax::HyraxScheme, zeromorph::Zeromorph};
 use toml_edit::{value, Array, DocumentMut, InlineTable, Item, Table, Value};
 
-#[derive(Serialize, Deserialize)]
-struct DecodedData {
-    bytecode: Vec<ELFInstruction>,
-    memory_init: Vec<(u64, u8)>,
-}
-
-struct FunctionAttributes {
-    pub func_name: String,
-    pub attributes: Attributes,
-}
-
-fn preprocess_and_save(func_name: &str, attributes: &Attributes, is_std: bool) -> Result<()> {
-    let mut program = Program::new("guest");
-
-    program.set_func(func_name);
-    program.set_std(is_std);
-    program.set_memory_size(attributes.memory_size);
-    program.set_stack_size(attributes.stack_size);
-    program.set_max_input_size(attributes.max_input_size);
-    program.set_max_output_size(attributes.max_output_size);
-
-    let (bytecode, memory_init) = program.decode();
-    let decoded_data = DecodedData {
-        bytecode,
-        memory_init,
+use crate::provable::{find_provable_functions, ProvableFunction};
+
+fn preprocess_and_save(function: &ProvableFunction, is_std: bool, out_dir: &str) -> Result<()> {
+    let mut program = function.program(is_std);
+    let buf = match function.attributes.pcs {
+        CommitmentSchemeKind::Hyrax => function
+            .preprocess::<HyraxScheme<G1Projective>>(&mut program)?
+            .verifier_preprocessing()
+            .serialize_to_bytes()?,
+        CommitmentSchemeKind::HyperKZG => function
+            .preprocess::<HyperKZG<Bn254>>(&mut program)?
+            .verifier_preprocessing()
+            .serialize_to_bytes()?,
+        CommitmentSchemeKind::Zeromorph => function
+            .preprocess::<Zeromorph<Bn254>>(&mut program)?
+            .verifier_preprocessing()
+            .serialize_to_bytes()?,
     };
 
-    let mut buf = Vec::new();
-    decoded_data.serialize(&mut Serializer::new(&mut buf))?;
-
-    let target_dir = Path::new("target/wasm32-unknown-unknown/release");
-    fs::create_dir_all(target_dir)?;
-
-    let output_path = target_dir.join(format!("preprocessed_{}.bin", func_name));
+    let output_path = Path::new(out_dir).join(format!("preprocessed_{}.bin", function.func_name));
     let mut file = File::create(output_path)?;
     file.write_all(&buf)?;
     Ok(())
 }
 
-fn extract_provable_functions() -> Vec<FunctionAttributes> {
-    let content = fs::read_to_string("guest/src/lib.rs").expect("Unable to read file");
-    let syntax: syn::File = syn::parse_file(&content).expect("Unable to parse file");
-
-    syntax
-        .items
-        .iter()
-        .filter_map(|item| {
-            if let syn::Item::Fn(ItemFn { attrs, sig, .. }) = item {
-                if let Some(provable_attr) = attrs.iter().find(|attr| is_provable(attr)) {
-                    let meta = provable_attr.parse_meta().expect("Unable to parse meta");
-                    if let Meta::List(meta_list) = meta {
-                        let attributes =
-                            parse_attributes(&meta_list.nested.iter().cloned().collect());
-                        return Some(FunctionAttributes {
-                            func_name: sig.ident.to_string(),
-                            attributes,
-                        });
-                    }
-                }
-            }
-            None
-        })
-        .collect()
-}
-
-fn is_provable(attr: &Attribute) -> bool {
-    if attr.path.segments.len() == 2 {
-        let segments: Vec<&PathSegment> = attr.path.segments.iter().collect();
-        if let [first, second] = segments.as_slice() {
-            return first.ident == "jolt" && second.ident == "provable";
-        }
-    }
-    false
-}
-
 fn get_project_name() -> Option<String> {
     let content = fs::read_to_string("Cargo.toml").ok()?;
     let doc = content.parse::<DocumentMut>().ok()?;
@@ -99,35 +49,67 @@ fn get_project_name() -> Option<String> {
     })
 }
 
-fn is_std() -> Option<bool> {
-    let content = fs::read_to_string("guest/Cargo.toml").expect("Failed to read Cargo.toml");
-    let doc = content
-        .parse::<DocumentMut>()
-        .expect("Failed to parse Cargo.toml");
-
-    let dependencies = doc["dependencies"]["jolt"].as_inline_table()?;
-    let package = dependencies.get("package")?.as_str()?;
-
-    let git = dependencies
-        .get("git")
-        .expect("Failed to get git-path")
-        .as_str()
-        .expect("Failed to get git-path as string");
-
-    if package == "jolt-sdk" && git == "https://github.com/a16z/jolt" {
-        return Some(
-            dependencies
-                .get("features")
-                .and_then(|v| v.as_array())
-                .map_or(false, |features| {
-                    features.iter().any(|f| f.as_str() == Some("guest-std"))
-                }),
-        );
+/// Whether the guest is built with the `guest-std` feature of `jolt-sdk`. Reads the resolved
+/// feature set from `cargo metadata`, so this works regardless of where `jolt-sdk` comes from
+/// (git, path or registry) and of features enabled indirectly.
+pub fn is_std() -> Result<bool> {
+    let output = std::process::Command::new("cargo")
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--manifest-path",
+            "guest/Cargo.toml",
+        ])
+        .output()?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
     }
-    None
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+    let guest_manifest = fs::canonicalize("guest/Cargo.toml")?;
+    let packages = metadata["packages"]
+        .as_array()
+        .ok_or_else(|| eyre!("cargo metadata is missing packages"))?;
+    let package_name = |id: &str| {
+        packages
+            .iter()
+            .find(|package| package["id"] == id)
+            .and_then(|package| package["name"].as_str())
+    };
+    let guest_id = packages
+        .iter()
+        .find(|package| {
+            package["manifest_path"]
+                .as_str()
+                .and_then(|path| fs::canonicalize(path).ok())
+                .is_some_and(|path| path == guest_manifest)
+        })
+        .and_then(|package| package["id"].as_str())
+        .ok_or_else(|| eyre!("guest package not found in cargo metadata"))?;
+
+    let nodes = metadata["resolve"]["nodes"]
+        .as_array()
+        .ok_or_else(|| eyre!("cargo metadata is missing the dependency graph"))?;
+    let node = |id: &str| nodes.iter().find(|node| node["id"] == id);
+
+    let sdk_id = node(guest_id)
+        .and_then(|guest| guest["deps"].as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|dep| dep["pkg"].as_str())
+        .find(|id| package_name(id) == Some("jolt-sdk"))
+        .ok_or_else(|| eyre!("guest does not depend on jolt-sdk"))?;
+
+    Ok(node(sdk_id)
+        .and_then(|sdk| sdk["features"].as_array())
+        .is_some_and(|features| features.iter().any(|f| f.as_str() == Some("guest-std"))))
 }
 
-fn create_index_html(func_names: Vec<String>) -> Result<()> {
+fn create_index_html(func_names: Vec<String>, out_dir: &str) -> Result<()> {
     let func_names_with_verify_prefix: Vec<String> = func_names
         .iter()
         .map(|name| format!("verify_{}", name))
@@ -140,6 +122,8 @@ fn create_index_html(func_names: Vec<String>) -> Result<()> {
             r#"
     <div style="margin-bottom: 10px;">
         <input type="file" id="proofFile_{0}" />
+        <input type="text" id="inputs_{0}" placeholder="Inputs (postcard hex)" />
+        <input type="text" id="outputs_{0}" placeholder="Outputs (postcard hex)" />
         <button id="verifyButton_{0}">Verify Proof for {0}-Function</button>
     </div>
 "#,
@@ -150,7 +134,16 @@ fn create_index_html(func_names: Vec<String>) -> Result<()> {
     html_content.push_str(&format!(
         r#"
     <script type="module">
-        import init, {{ {} }} from './pkg/{}.js';
+        import init, {{ {} }} from './{}.js';
+
+        function hexToBytes(hex) {{
+            hex = hex.trim().replace(/^0x/, '');
+            const bytes = new Uint8Array(hex.length / 2);
+            for (let i = 0; i < bytes.length; i++) {{
+                bytes[i] = parseInt(hex.substr(i * 2, 2), 16);
+            }}
+            return bytes;
+        }}
 
         async function run() {{
             await init();
@@ -177,12 +170,19 @@ fn create_index_html(func_names: Vec<String>) -> Result<()> {
                     const proofData = new Uint8Array(proofArrayBuffer);
 
                     // Fetch preprocessing data and prepare wasm binary to json conversion
-                    const response = await fetch('target/wasm32-unknown-unknown/release/preprocessed_{0}.bin')
+                    const response = await fetch('./preprocessed_{0}.bin')
                     const wasmBinary = await response.arrayBuffer();
                     const wasmData = new Uint8Array(wasmBinary);
 
-                    const result = verify_{0}(wasmData, proofData);
-                    alert(result ? "Proof is valid!" : "Proof is invalid.");
+                    const inputs = hexToBytes(document.getElementById('inputs_{0}').value);
+                    const outputs = hexToBytes(document.getElementById('outputs_{0}').value);
+
+                    try {{
+                        verify_{0}(wasmData, proofData, inputs, outputs);
+                        alert("Proof is valid!");
+                    }} catch (error) {{
+                        alert(`Proof is invalid: ${{error}}`);
+                    }}
                 }};
 
                 reader.readAsArrayBuffer(file);
@@ -194,7 +194,7 @@ fn create_index_html(func_names: Vec<String>) -> Result<()> {
 
     html_content.push_str(HTML_TAIL);
 
-    let mut file = File::create("index.html")?;
+    let mut file = File::create(Path::new(out_dir).join("index.html"))?;
     file.write_all(html_content.as_bytes())?;
     Ok(())
 }
@@ -212,7 +212,6 @@ pub fn modify_cargo_toml(name: &str) -> Result<()> {
         });
         dependencies.insert("serde_json", toml_edit::value("1.0"));
         dependencies.insert("serde-wasm-bindgen", toml_edit::value("=0.6.5"));
-        dependencies.insert("rmp-serde", toml_edit::value("1.3.0"));
     }
 
     {
@@ -266,21 +265,54 @@ pub fn modify_cargo_toml(name: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn build_wasm() {
+/// The `wasm-pack` target the verifiers are built for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum WasmTarget {
+    /// ES module loaded directly by a browser
+    Web,
+    /// CommonJS module for Node.js
+    Nodejs,
+    /// ES module for bundlers such as webpack or vite
+    Bundler,
+}
+
+impl WasmTarget {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WasmTarget::Web => "web",
+            WasmTarget::Nodejs => "nodejs",
+            WasmTarget::Bundler => "bundler",
+        }
+    }
+}
+
+pub fn build_wasm(target: WasmTarget, out_dir: &str, no_html: bool) {
     println!("Building the project with wasm-pack...");
-    let functions = extract_provable_functions();
+    let functions = match find_provable_functions(Path::new("guest")) {
+        Ok(functions) => functions,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }
+    };
     let function_names: Vec<String> = functions.iter().map(|f| f.func_name.clone()).collect();
+    let wasm_function_names: Vec<String> = functions
+        .iter()
+        .filter(|f| f.attributes.wasm)
+        .map(|f| f.func_name.clone())
+        .collect();
     let is_std = is_std().expect("Failed to check if std feature is enabled");
-    for function in functions {
-        preprocess_and_save(&function.func_name, &function.attributes, is_std)
-            .expect("Failed to preprocess functions");
-    }
-
-    create_index_html(function_names).expect("Failed to create example index.html");
 
     // todo implement test if wasm-pack is installed
     let output = std::process::Command::new("wasm-pack")
-        .args(["build", "--release", "--target", "web"])
+        .args([
+            "build",
+            "--release",
+            "--target",
+            target.as_str(),
+            "--out-dir",
+            out_dir,
+        ])
         .output()
         .expect("Failed to execute wasm-pack command");
 
@@ -290,6 +322,88 @@ pub fn build_wasm() {
         eprintln!("stderr: {}", String::from_utf8_lossy(&output.stderr));
         panic!("wasm-pack build failed");
     }
+
+    // Everything else is written next to the package, so the output directory can be
+    // served or shipped on its own.
+    for function in functions {
+        preprocess_and_save(&function, is_std, out_dir).expect("Failed to preprocess functions");
+    }
+
+    // The example page loads the package as an ES module straight from the browser,
+    // which only works for the web target.
+    if !no_html {
+        if target == WasmTarget::Web {
+            create_index_html(function_names, out_dir)
+                .expect("Failed to create example index.html");
+        } else {
+            println!(
+                "Skipping index.html, it is only generated for the web target (got {})",
+                target.as_str()
+            );
+        }
+    }
+
+    create_verifiers_d_ts(&wasm_function_names, out_dir)
+        .expect("Failed to create TypeScript declarations");
+}
+
+/// Writes `verifiers.d.ts` next to the wasm-pack output. It declares a `JoltVerifiers`
+/// interface with the verifier exports of every WASM-verifiable function, which the
+/// generated package module satisfies.
+fn create_verifiers_d_ts(func_names: &[String], out_dir: &str) -> Result<()> {
+    let project_name = get_project_name().unwrap();
+
+    let mut content = format!(
+        r#"// Generated by `jolt build-wasm`. Do not edit.
+import type {{ VerifyError, VerifyErrorKind }} from "./{0}";
+
+export type {{ VerifyError, VerifyErrorKind }};
+
+/** Names of all functions marked with `#[jolt::provable(wasm)]`. */
+export type ProvableFunction = {1};
+
+/**
+ * The verifiers exported by `./{0}`. Every function throws a `VerifyError` if the proof is
+ * rejected. Inputs and outputs are postcard-encoded, as in the proof's `program_io`.
+ */
+export interface JoltVerifiers {{
+"#,
+        project_name,
+        if func_names.is_empty() {
+            "never".to_string()
+        } else {
+            func_names
+                .iter()
+                .map(|name| format!("\"{}\"", name))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        }
+    );
+
+    for func_name in func_names {
+        content.push_str(&format!(
+            r#"    /** Verifies a proof of `{0}` for the given inputs and outputs. */
+    verify_{0}(
+        preprocessing: Uint8Array,
+        proof: Uint8Array,
+        inputs: Uint8Array,
+        outputs: Uint8Array,
+    ): void;
+    /** Verifies a proof of `{0}` for the given inputs and returns its decoded output. */
+    verify_{0}_with_output(
+        preprocessing: Uint8Array,
+        proof: Uint8Array,
+        inputs: Uint8Array,
+    ): unknown;
+"#,
+            func_name
+        ));
+    }
+    content.push_str("}\n");
+
+    let mut file = File::create(Path::new(out_dir).join("verifiers.d.ts"))?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
 }
 
 const HTML_HEAD: &str = r#"
@@ -0,0 +1,290 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use common::rv_trace::MemoryState;
+use eyre::{eyre, Result};
+use jolt_core::host::{Debugger, StopReason};
+use rustc_demangle::demangle;
+
+use crate::build_wasm::is_std;
+use crate::provable::find_provable_function;
+use crate::verify::{encode_hex, parse_bytes_arg};
+
+const REGISTER_NAMES: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
+/// The most bytes the `memory` command prints at once.
+const MAX_MEMORY_LEN: u64 = 4096;
+
+const HELP: &str = "\
+Commands:
+  break [<address|function>]    set a breakpoint, or list breakpoints (b)
+  delete <address|function>     remove a breakpoint (d)
+  step [<count>]                execute instructions (s)
+  continue                      run until a breakpoint, watched I/O or termination (c)
+  registers                     print the registers (r)
+  memory <address> [<length>]   print guest memory, 64 bytes by default, 4096 at most (x)
+  io                            print the program inputs and outputs
+  watch <on|off>                stop when the guest reads inputs or writes outputs
+  disassemble                   print the instruction at the program counter
+  help                          print this message (h)
+  quit                          exit the debugger (q)";
+
+pub fn debug(func: &str, input: Option<String>) {
+    if let Err(err) = run_debugger(func, input.as_deref()) {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run_debugger(func: &str, input: Option<&str>) -> Result<()> {
+    let function = find_provable_function(Path::new("guest"), func)?;
+    let input = parse_bytes_arg(input, "input")?.unwrap_or_default();
+
+    let mut program = function.program(is_std()?);
+    program.set_input_bytes(&input);
+    let mut debugger = program.debugger()?;
+    let max_cycles = function.attributes.max_cycles;
+
+    println!("Debugging `{}`, type `help` for a list of commands", func);
+    print_location(&mut debugger);
+
+    let mut lines = io::stdin().lines();
+    loop {
+        print!("(jolt) ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(());
+        };
+        let line = line?;
+        let args: Vec<&str> = line.split_whitespace().collect();
+        let Some((command, args)) = args.split_first() else {
+            continue;
+        };
+
+        let result = match *command {
+            "break" | "b" => set_breakpoint(&mut debugger, args.first().copied()),
+            "delete" | "d" => delete_breakpoint(&mut debugger, args.first().copied()),
+            "step" | "s" => step(&mut debugger, args.first().copied()),
+            "continue" | "c" => resume(&mut debugger, max_cycles),
+            "registers" | "r" => {
+                print_registers(&debugger);
+                Ok(())
+            }
+            "memory" | "x" => print_memory(&debugger, args),
+            "io" => {
+                print_io(&debugger);
+                Ok(())
+            }
+            "watch" => set_watch_io(&mut debugger, args.first().copied()),
+            "disassemble" | "disas" => {
+                println!("{}", debugger.disassemble());
+                Ok(())
+            }
+            "help" | "h" => {
+                println!("{}", HELP);
+                Ok(())
+            }
+            "quit" | "q" => return Ok(()),
+            _ => Err(eyre!("unknown command `{}`, try `help`", command)),
+        };
+        if let Err(err) = result {
+            println!("Error: {}", err);
+        }
+    }
+}
+
+fn set_breakpoint(debugger: &mut Debugger, location: Option<&str>) -> Result<()> {
+    let Some(location) = location else {
+        for address in debugger.breakpoints() {
+            println!("{:#x}{}", address, function_suffix(debugger, address));
+        }
+        return Ok(());
+    };
+    let address = resolve_location(debugger, location)?;
+    if debugger.add_breakpoint(address) {
+        println!(
+            "Breakpoint at {:#x}{}",
+            address,
+            function_suffix(debugger, address)
+        );
+    }
+    Ok(())
+}
+
+fn delete_breakpoint(debugger: &mut Debugger, location: Option<&str>) -> Result<()> {
+    let location = location.ok_or_else(|| eyre!("usage: delete <address|function>"))?;
+    let address = resolve_location(debugger, location)?;
+    if !debugger.remove_breakpoint(address) {
+        return Err(eyre!("no breakpoint at {:#x}", address));
+    }
+    Ok(())
+}
+
+fn set_watch_io(debugger: &mut Debugger, enabled: Option<&str>) -> Result<()> {
+    match enabled {
+        Some("on") => debugger.set_watch_io(true),
+        Some("off") => debugger.set_watch_io(false),
+        _ => return Err(eyre!("usage: watch <on|off>")),
+    }
+    Ok(())
+}
+
+fn step(debugger: &mut Debugger, count: Option<&str>) -> Result<()> {
+    let count = match count {
+        Some(count) => count.parse()?,
+        None => 1,
+    };
+    for _ in 0..count {
+        match debugger.step()? {
+            StopReason::Step => {}
+            reason => {
+                report_stop(debugger, reason);
+                return Ok(());
+            }
+        }
+    }
+    print_location(debugger);
+    Ok(())
+}
+
+/// Resumes execution, stopping once the guest has run for `max_cycles` cycles in total.
+fn resume(debugger: &mut Debugger, max_cycles: u64) -> Result<()> {
+    let reason = debugger.resume(max_cycles.saturating_sub(debugger.cycles()))?;
+    report_stop(debugger, reason);
+    Ok(())
+}
+
+fn report_stop(debugger: &mut Debugger, reason: StopReason) {
+    match reason {
+        StopReason::Step => {}
+        StopReason::Breakpoint(address) => println!("Breakpoint at {:#x}", address),
+        StopReason::Io(MemoryState::Read { address, value }) => {
+            println!("Input read at {:#x}: {:#x}", address, value)
+        }
+        StopReason::Io(MemoryState::Write {
+            address,
+            post_value,
+        }) => println!("Output written at {:#x}: {:#x}", address, post_value),
+        StopReason::Terminated => {
+            println!("Guest terminated after {} cycles", debugger.cycles());
+            if let Some(guest_panic) = debugger.device().guest_panic() {
+                println!("{}", guest_panic);
+            }
+            return;
+        }
+        StopReason::CycleLimit => println!(
+            "Guest did not terminate within {} cycles",
+            debugger.cycles()
+        ),
+    }
+    print_location(debugger);
+}
+
+fn print_location(debugger: &mut Debugger) {
+    let pc = debugger.pc();
+    println!("{:#x}{}", pc, function_suffix(debugger, pc));
+    println!("{}", debugger.disassemble());
+}
+
+fn function_suffix(debugger: &Debugger, address: u64) -> String {
+    match debugger.function_at(address) {
+        Some(symbol) => format!(
+            " in {:#} + {:#x}",
+            demangle(&symbol.name),
+            address - symbol.address
+        ),
+        None => String::new(),
+    }
+}
+
+/// Parses an address, or finds the function with the given mangled, demangled or
+/// unqualified name.
+fn resolve_location(debugger: &Debugger, location: &str) -> Result<u64> {
+    if let Some(hex) = location.strip_prefix("0x") {
+        return Ok(u64::from_str_radix(hex, 16)?);
+    }
+    if let Ok(address) = location.parse() {
+        return Ok(address);
+    }
+
+    let suffix = format!("::{}", location);
+    let matches: Vec<_> = debugger
+        .symbols()
+        .iter()
+        .filter(|symbol| {
+            let demangled = format!("{:#}", demangle(&symbol.name));
+            symbol.name == location || demangled == location || demangled.ends_with(&suffix)
+        })
+        .collect();
+    match matches.as_slice() {
+        [] => Err(eyre!("no function named `{}`", location)),
+        [symbol] => Ok(symbol.address),
+        _ => {
+            let names: Vec<_> = matches
+                .iter()
+                .map(|symbol| format!("{:#}", demangle(&symbol.name)))
+                .collect();
+            Err(eyre!(
+                "`{}` is ambiguous, candidates: {}",
+                location,
+                names.join(", ")
+            ))
+        }
+    }
+}
+
+fn print_registers(debugger: &Debugger) {
+    println!("{:<8}{:#010x}", "pc", debugger.pc());
+    for (reg, name) in REGISTER_NAMES.iter().enumerate() {
+        let value = debugger.register(reg as u8);
+        println!(
+            "{:<8}{:#010x} {}",
+            format!("x{}/{}", reg, name),
+            value,
+            value
+        );
+    }
+}
+
+fn print_memory(debugger: &Debugger, args: &[&str]) -> Result<()> {
+    let address = match args.first() {
+        Some(address) => resolve_location(debugger, address)?,
+        None => return Err(eyre!("usage: memory <address> [<length>]")),
+    };
+    let len = match args.get(1) {
+        Some(len) => len.parse()?,
+        None => 64,
+    };
+    if len > MAX_MEMORY_LEN {
+        return Err(eyre!("cannot print more than {} bytes", MAX_MEMORY_LEN));
+    }
+    let bytes = debugger.read_memory(address, len).ok_or_else(|| {
+        eyre!(
+            "{:#x}..{:#x} is not mapped",
+            address,
+            address.saturating_add(len)
+        )
+    })?;
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        let hex: Vec<_> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+        println!("{:#010x}  {}", address + row as u64 * 16, hex.join(" "));
+    }
+    Ok(())
+}
+
+fn print_io(debugger: &Debugger) {
+    let device = debugger.device();
+    println!("inputs:  {}", encode_hex(&device.inputs));
+    println!("outputs: {}", encode_hex(&device.outputs));
+    println!("log:     {:?}", String::from_utf8_lossy(debugger.log()));
+    println!("panic:   {}", device.panic);
+    if let Some(guest_panic) = device.guest_panic() {
+        println!("         {}", guest_panic);
+    }
+}
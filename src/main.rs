@@ -1,8 +1,14 @@
+mod analyze;
 mod build_wasm;
+mod debug;
+mod provable;
+mod prove;
+mod verify;
 
 use std::{
     fs::{self, File},
     io::Write,
+    path::PathBuf,
 };
 
 use clap::{Parser, Subcommand};
@@ -10,8 +16,13 @@ use eyre::Result;
 use rand::prelude::SliceRandom;
 use sysinfo::System;
 
-use build_wasm::{build_wasm, modify_cargo_toml};
+use analyze::analyze;
+use build_wasm::{build_wasm, modify_cargo_toml, WasmTarget};
+use common::attributes::CommitmentSchemeKind;
+use debug::debug;
 use jolt_core::host::toolchain;
+use prove::prove;
+use verify::verify;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -33,7 +44,68 @@ enum Command {
     /// Installs the required RISC-V toolchains for Rust
     InstallToolchain,
     /// Handles preprocessing and generates WASM compatible files
-    BuildWasm,
+    BuildWasm {
+        /// wasm-pack target to build for
+        #[arg(long, value_enum, default_value_t = WasmTarget::Web)]
+        target: WasmTarget,
+        /// Output directory of the generated package, preprocessing files and index.html
+        #[arg(long, default_value = "pkg")]
+        out_dir: String,
+        /// Skip generating the example index.html
+        #[arg(long)]
+        no_html: bool,
+    },
+    /// Estimates the cost of proving a provable function of the guest, running only the tracer
+    Analyze {
+        /// Name of the provable function
+        #[arg(long)]
+        func: String,
+        /// Postcard-encoded inputs, as a hex string or a file
+        #[arg(long)]
+        input: Option<String>,
+    },
+    /// Runs a provable function of the guest in an interactive debugger
+    Debug {
+        /// Name of the provable function
+        #[arg(long)]
+        func: String,
+        /// Postcard-encoded inputs, as a hex string or a file
+        #[arg(long)]
+        input: Option<String>,
+    },
+    /// Proves a provable function of the guest and writes the proof to a file
+    Prove {
+        /// Name of the provable function
+        #[arg(long)]
+        func: String,
+        /// Postcard-encoded inputs, as a hex string or a file
+        #[arg(long)]
+        input: Option<String>,
+        /// Where to write the proof [default: proof_<func>.bin]
+        #[arg(long)]
+        proof: Option<PathBuf>,
+        /// Where to write the JSON summary [default: the proof path with a .json extension]
+        #[arg(long)]
+        summary: Option<PathBuf>,
+    },
+    /// Verifies a proof against the preprocessing produced by build-wasm
+    Verify {
+        /// Verifier preprocessing, e.g. pkg/preprocessed_<fn>.bin
+        #[arg(long)]
+        preprocessing: PathBuf,
+        /// Proof to verify
+        #[arg(long)]
+        proof: PathBuf,
+        /// Expected postcard-encoded inputs, as a hex string or a file
+        #[arg(long)]
+        inputs: Option<String>,
+        /// Expected postcard-encoded outputs, as a hex string or a file
+        #[arg(long)]
+        outputs: Option<String>,
+        /// Commitment scheme the proof was generated with
+        #[arg(long, default_value = "hyrax")]
+        pcs: CommitmentSchemeKind,
+    },
 }
 
 fn main() {
@@ -41,7 +113,26 @@ fn main() {
     match cli.command {
         Command::New { name, wasm } => create_project(name, wasm),
         Command::InstallToolchain => install_toolchain(),
-        Command::BuildWasm => build_wasm(),
+        Command::BuildWasm {
+            target,
+            out_dir,
+            no_html,
+        } => build_wasm(target, &out_dir, no_html),
+        Command::Analyze { func, input } => analyze(&func, input),
+        Command::Debug { func, input } => debug(&func, input),
+        Command::Prove {
+            func,
+            input,
+            proof,
+            summary,
+        } => prove(&func, input, proof, summary),
+        Command::Verify {
+            preprocessing,
+            proof,
+            inputs,
+            outputs,
+            pcs,
+        } => verify(pcs, &preprocessing, &proof, inputs, outputs),
     }
 }
 
@@ -186,7 +277,7 @@ ark-serialize = { git = "https://github.com/a16z/arkworks-algebra", branch = "op
 const HOST_MAIN: &str = r#"pub fn main() {
     let (prove_fib, verify_fib) = guest::build_fib();
 
-    let (output, proof) = prove_fib(50);
+    let (output, proof) = prove_fib(50).unwrap();
     let is_valid = verify_fib(proof);
 
     println!("output: {}", output);
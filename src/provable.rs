@@ -0,0 +1,299 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ark_bn254::Fr;
+use common::attributes::{try_parse_attributes, Attributes};
+use eyre::{eyre, Result};
+use jolt_core::host::{Program, TracerError};
+use jolt_core::jolt::vm::{
+    rv32i_vm::{RV32IJoltVM, C, M},
+    Jolt, JoltPreprocessing,
+};
+use jolt_core::poly::commitment::commitment_scheme::CommitmentScheme;
+use syn::{Attribute, Item, ItemUse, Lit, Meta, UseTree};
+
+pub struct ProvableFunction {
+    pub func_name: String,
+    pub attributes: Attributes,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl ProvableFunction {
+    /// The guest program of this function, configured like the macro-generated
+    /// `preprocess_*` function does.
+    pub fn program(&self, is_std: bool) -> Program {
+        let mut program = Program::new("guest");
+        program.set_func(&self.func_name);
+        program.set_std(is_std);
+        program.set_memory_size(self.attributes.memory_size);
+        program.set_stack_size(self.attributes.stack_size);
+        program.set_max_input_size(self.attributes.max_input_size);
+        program.set_max_output_size(self.attributes.max_output_size);
+        program.set_max_cycles(self.attributes.max_cycles);
+        program
+    }
+
+    pub fn preprocess<PCS: CommitmentScheme<Field = Fr>>(
+        &self,
+        program: &mut Program,
+    ) -> Result<JoltPreprocessing<Fr, PCS>, TracerError> {
+        let (bytecode, memory_init) = program.decode()?;
+        Ok(<RV32IJoltVM as Jolt<_, PCS, C, M>>::preprocess(
+            bytecode,
+            memory_init,
+            self.attributes.max_bytecode_size as usize,
+            1 << 20,
+            self.attributes.max_trace_length as usize,
+        ))
+    }
+}
+
+/// Finds all functions marked with `#[jolt::provable]` in the guest crate rooted at
+/// `guest_dir`, following `mod` declarations from `src/lib.rs` into submodules and
+/// resolving `use` imports of the macro, e.g. `use jolt::provable;` or
+/// `use jolt::provable as prove;`.
+pub fn find_provable_functions(guest_dir: &Path) -> Result<Vec<ProvableFunction>> {
+    let root = guest_dir.join("src").join("lib.rs");
+    let mut functions = Vec::new();
+    visit_file(&root, &guest_dir.join("src"), &mut functions)?;
+
+    let mut seen = HashMap::new();
+    for function in &functions {
+        if let Some((file, line)) = seen.insert(
+            function.func_name.as_str(),
+            (function.file.display(), function.line),
+        ) {
+            return Err(eyre!(
+                "{}:{}: provable function `{}` is already defined at {}:{}",
+                function.file.display(),
+                function.line,
+                function.func_name,
+                file,
+                line
+            ));
+        }
+    }
+
+    Ok(functions)
+}
+
+/// Finds the provable function named `func` in the guest crate rooted at `guest_dir`.
+pub fn find_provable_function(guest_dir: &Path, func: &str) -> Result<ProvableFunction> {
+    let functions = find_provable_functions(guest_dir)?;
+    let names: Vec<String> = functions.iter().map(|f| f.func_name.clone()).collect();
+    functions
+        .into_iter()
+        .find(|function| function.func_name == func)
+        .ok_or_else(|| {
+            eyre!(
+                "no provable function named `{}`, available functions: {}",
+                func,
+                names.join(", ")
+            )
+        })
+}
+
+/// Names under which the provable macro is reachable from within a module.
+#[derive(Default)]
+struct MacroScope {
+    /// Names the `jolt` crate is known as, e.g. via `use jolt as sdk;`.
+    crate_aliases: HashSet<String>,
+    /// Names the macro itself is imported as, e.g. via `use jolt::provable;`.
+    macro_aliases: HashSet<String>,
+}
+
+impl MacroScope {
+    fn new(items: &[Item]) -> Self {
+        let mut scope = Self::default();
+        scope.crate_aliases.insert("jolt".to_string());
+
+        let uses: Vec<&ItemUse> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Use(item_use) => Some(item_use),
+                _ => None,
+            })
+            .collect();
+
+        // `use jolt as sdk;` may come after `use sdk::provable;`, so crate aliases are
+        // collected before the imports that go through them.
+        for item_use in &uses {
+            scope.add_crate_aliases(&item_use.tree);
+        }
+        for item_use in &uses {
+            scope.add_macro_aliases(&item_use.tree, false);
+        }
+
+        scope
+    }
+
+    fn add_crate_aliases(&mut self, tree: &UseTree) {
+        match tree {
+            UseTree::Rename(rename) if rename.ident == "jolt" => {
+                self.crate_aliases.insert(rename.rename.to_string());
+            }
+            UseTree::Group(group) => group
+                .items
+                .iter()
+                .for_each(|tree| self.add_crate_aliases(tree)),
+            _ => {}
+        }
+    }
+
+    /// `in_jolt` is set once the path so far refers to the `jolt` crate.
+    fn add_macro_aliases(&mut self, tree: &UseTree, in_jolt: bool) {
+        match tree {
+            UseTree::Path(path)
+                if !in_jolt && self.crate_aliases.contains(&path.ident.to_string()) =>
+            {
+                self.add_macro_aliases(&path.tree, true);
+            }
+            UseTree::Name(name) if in_jolt && name.ident == "provable" => {
+                self.macro_aliases.insert("provable".to_string());
+            }
+            UseTree::Rename(rename) if in_jolt && rename.ident == "provable" => {
+                self.macro_aliases.insert(rename.rename.to_string());
+            }
+            UseTree::Glob(_) if in_jolt => {
+                self.macro_aliases.insert("provable".to_string());
+            }
+            UseTree::Group(group) => group
+                .items
+                .iter()
+                .for_each(|tree| self.add_macro_aliases(tree, in_jolt)),
+            _ => {}
+        }
+    }
+
+    fn is_provable(&self, attr: &Attribute) -> bool {
+        let segments: Vec<String> = attr
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        match segments.as_slice() {
+            [name] => self.macro_aliases.contains(name),
+            [krate, name] => self.crate_aliases.contains(krate) && name == "provable",
+            _ => false,
+        }
+    }
+}
+
+/// Collects the provable functions of the module in `file`. Submodules declared in it
+/// are looked up relative to `module_dir`.
+fn visit_file(file: &Path, module_dir: &Path, functions: &mut Vec<ProvableFunction>) -> Result<()> {
+    let content = fs::read_to_string(file)
+        .map_err(|err| eyre!("{}: unable to read file: {}", file.display(), err))?;
+    let syntax = syn::parse_file(&content)
+        .map_err(|err| eyre!("{}: {}", location(file, err.span()), err))?;
+    visit_items(&syntax.items, file, module_dir, functions)
+}
+
+fn visit_items(
+    items: &[Item],
+    file: &Path,
+    module_dir: &Path,
+    functions: &mut Vec<ProvableFunction>,
+) -> Result<()> {
+    let scope = MacroScope::new(items);
+
+    for item in items {
+        match item {
+            Item::Fn(item_fn) => {
+                if let Some(attr) = item_fn.attrs.iter().find(|attr| scope.is_provable(attr)) {
+                    let attributes = parse_provable_attr(attr)
+                        .map_err(|err| eyre!("{}: {}", location(file, err.span()), err))?;
+                    functions.push(ProvableFunction {
+                        func_name: item_fn.sig.ident.to_string(),
+                        attributes,
+                        file: file.to_path_buf(),
+                        line: item_fn.sig.ident.span().start().line,
+                    });
+                }
+            }
+            Item::Mod(item_mod) => {
+                let name = item_mod.ident.to_string();
+                let path_attr = path_attribute(&item_mod.attrs)
+                    .map_err(|err| eyre!("{}: {}", location(file, err.span()), err))?;
+
+                if let Some((_, items)) = &item_mod.content {
+                    let module_dir = match path_attr {
+                        Some(path) => module_dir.join(path),
+                        None => module_dir.join(&name),
+                    };
+                    visit_items(items, file, &module_dir, functions)?;
+                } else {
+                    let (module_file, child_dir) = match path_attr {
+                        Some(path) => {
+                            let module_file = module_dir.join(path);
+                            let child_dir =
+                                module_file.parent().unwrap_or(module_dir).to_path_buf();
+                            (module_file, child_dir)
+                        }
+                        None => resolve_module_file(module_dir, &name).ok_or_else(|| {
+                            eyre!(
+                                "{}: file not found for module `{}`",
+                                location(file, item_mod.ident.span()),
+                                name
+                            )
+                        })?,
+                    };
+                    visit_file(&module_file, &child_dir, functions)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves `mod name;` to `name.rs` or `name/mod.rs`, returning the file along with the
+/// directory its own submodules live in.
+fn resolve_module_file(module_dir: &Path, name: &str) -> Option<(PathBuf, PathBuf)> {
+    let file = module_dir.join(format!("{}.rs", name));
+    if file.is_file() {
+        return Some((file, module_dir.join(name)));
+    }
+    let file = module_dir.join(name).join("mod.rs");
+    if file.is_file() {
+        return Some((file, module_dir.join(name)));
+    }
+    None
+}
+
+fn path_attribute(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path.is_ident("path")) else {
+        return Ok(None);
+    };
+    match attr.parse_meta()? {
+        Meta::NameValue(meta) => match meta.lit {
+            Lit::Str(lit) => Ok(Some(lit.value())),
+            lit => Err(syn::Error::new_spanned(lit, "expected string literal")),
+        },
+        meta => Err(syn::Error::new_spanned(
+            meta,
+            "expected `#[path = \"...\"]`",
+        )),
+    }
+}
+
+fn parse_provable_attr(attr: &Attribute) -> syn::Result<Attributes> {
+    match attr.parse_meta()? {
+        Meta::Path(_) => try_parse_attributes(&Vec::new()),
+        Meta::List(meta_list) => try_parse_attributes(&meta_list.nested.into_iter().collect()),
+        meta => Err(syn::Error::new_spanned(
+            meta,
+            "expected `#[jolt::provable]` or `#[jolt::provable(...)]`",
+        )),
+    }
+}
+
+fn location(file: &Path, span: proc_macro2::Span) -> String {
+    format!("{}:{}", file.display(), span.start().line)
+}
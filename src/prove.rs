@@ -0,0 +1,91 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use ark_bn254::{Bn254, Fr, G1Projective};
+use common::attributes::CommitmentSchemeKind;
+use eyre::Result;
+use jolt_core::jolt::vm::{
+    rv32i_vm::{RV32IJoltVM, RV32IProof, Serializable, C, M},
+    Jolt,
+};
+use jolt_core::poly::commitment::{
+    commitment_scheme::CommitmentScheme, hyperkzg::HyperKZG, hyrax::HyraxScheme,
+    zeromorph::Zeromorph,
+};
+use serde_json::json;
+
+use crate::build_wasm::is_std;
+use crate::provable::{find_provable_function, ProvableFunction};
+use crate::verify::{encode_hex, parse_bytes_arg};
+
+pub fn prove(func: &str, input: Option<String>, proof: Option<PathBuf>, summary: Option<PathBuf>) {
+    let proof_path = proof.unwrap_or_else(|| PathBuf::from(format!("proof_{}.bin", func)));
+    let summary_path = summary.unwrap_or_else(|| proof_path.with_extension("json"));
+
+    if let Err(err) = prove_function(func, input.as_deref(), &proof_path, &summary_path) {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+    println!("Proof written to {}", proof_path.display());
+    println!("Summary written to {}", summary_path.display());
+}
+
+fn prove_function(
+    func: &str,
+    input: Option<&str>,
+    proof_path: &Path,
+    summary_path: &Path,
+) -> Result<()> {
+    let function = &find_provable_function(Path::new("guest"), func)?;
+    let input = parse_bytes_arg(input, "input")?.unwrap_or_default();
+    let is_std = is_std()?;
+
+    let summary = match function.attributes.pcs {
+        CommitmentSchemeKind::Hyrax => {
+            prove_and_save::<HyraxScheme<G1Projective>>(function, is_std, &input, proof_path)?
+        }
+        CommitmentSchemeKind::HyperKZG => {
+            prove_and_save::<HyperKZG<Bn254>>(function, is_std, &input, proof_path)?
+        }
+        CommitmentSchemeKind::Zeromorph => {
+            prove_and_save::<Zeromorph<Bn254>>(function, is_std, &input, proof_path)?
+        }
+    };
+    fs::write(summary_path, serde_json::to_string_pretty(&summary)?)?;
+    Ok(())
+}
+
+/// Proves `function` on `input` and writes the proof to `proof_path`, returning a summary
+/// of the proven execution. Inputs and outputs are hex-encoded in the summary so they can
+/// be pasted into the generated `index.html`.
+fn prove_and_save<PCS: CommitmentScheme<Field = Fr>>(
+    function: &ProvableFunction,
+    is_std: bool,
+    input: &[u8],
+    proof_path: &Path,
+) -> Result<serde_json::Value> {
+    let mut program = function.program(is_std);
+    let preprocessing = function.preprocess::<PCS>(&mut program)?;
+
+    program.set_input_bytes(input);
+    let (io_device, trace, circuit_flags, log) = program.trace()?;
+    io::stderr().write_all(&log)?;
+    let (proof, commitments) =
+        <RV32IJoltVM as Jolt<Fr, PCS, C, M>>::prove(io_device, trace, circuit_flags, preprocessing);
+
+    let summary = json!({
+        "function": function.func_name,
+        "pcs": function.attributes.pcs.to_string(),
+        "inputs": encode_hex(&proof.program_io.inputs),
+        "outputs": encode_hex(&proof.program_io.outputs),
+        "panic": proof.program_io.panic,
+        "guest_panic": proof.program_io.guest_panic().map(|guest_panic| guest_panic.to_string()),
+        "trace_length": proof.trace_length,
+    });
+
+    RV32IProof { proof, commitments }.save_to_file(proof_path)?;
+    Ok(summary)
+}
@@ -0,0 +1,146 @@
+use std::{fs, path::Path};
+
+use ark_bn254::{Bn254, Fr, G1Projective};
+use common::{attributes::CommitmentSchemeKind, rv_trace::JoltDevice};
+use eyre::{eyre, Result};
+use jolt_core::jolt::vm::{
+    rv32i_vm::{RV32IJoltVM, RV32IProof, Serializable, C, M},
+    Jolt, JoltVerifierPreprocessing,
+};
+use jolt_core::poly::commitment::{
+    commitment_scheme::CommitmentScheme, hyperkzg::HyperKZG, hyrax::HyraxScheme,
+    zeromorph::Zeromorph,
+};
+
+pub fn verify(
+    pcs: CommitmentSchemeKind,
+    preprocessing: &Path,
+    proof: &Path,
+    inputs: Option<String>,
+    outputs: Option<String>,
+) {
+    let result = parse_bytes_arg(inputs.as_deref(), "inputs").and_then(|inputs| {
+        let outputs = parse_bytes_arg(outputs.as_deref(), "outputs")?;
+        match pcs {
+            CommitmentSchemeKind::Hyrax => verify_proof::<HyraxScheme<G1Projective>>(
+                preprocessing,
+                proof,
+                inputs.as_deref(),
+                outputs.as_deref(),
+            ),
+            CommitmentSchemeKind::HyperKZG => verify_proof::<HyperKZG<Bn254>>(
+                preprocessing,
+                proof,
+                inputs.as_deref(),
+                outputs.as_deref(),
+            ),
+            CommitmentSchemeKind::Zeromorph => verify_proof::<Zeromorph<Bn254>>(
+                preprocessing,
+                proof,
+                inputs.as_deref(),
+                outputs.as_deref(),
+            ),
+        }
+    });
+
+    match result {
+        Ok(()) => println!("Proof is valid"),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn verify_proof<PCS: CommitmentScheme<Field = Fr>>(
+    preprocessing_path: &Path,
+    proof_path: &Path,
+    inputs: Option<&[u8]>,
+    outputs: Option<&[u8]>,
+) -> Result<()> {
+    let preprocessing = JoltVerifierPreprocessing::<Fr, PCS>::from_file(preprocessing_path)
+        .map_err(|err| {
+            eyre!(
+                "failed to read preprocessing from {}: {}",
+                preprocessing_path.display(),
+                err
+            )
+        })?;
+    let proof = RV32IProof::<PCS>::from_file(proof_path).map_err(|err| {
+        eyre!(
+            "failed to read proof from {}: {}",
+            proof_path.display(),
+            err
+        )
+    })?;
+
+    let program_io = proof.proof.program_io.clone();
+    let result = check_program_io(&program_io, inputs, outputs).and_then(|()| {
+        <RV32IJoltVM as Jolt<Fr, PCS, C, M>>::verify(
+            preprocessing.into(),
+            proof.proof,
+            proof.commitments,
+        )
+        .map_err(|err| eyre!("proof verification failed: {}", err))
+    });
+    if result.is_err() {
+        print_program_io(&program_io);
+    }
+    result
+}
+
+/// A valid proof only attests to the program IO it carries, so it is checked against the
+/// expected inputs and outputs before the proof itself.
+fn check_program_io(
+    program_io: &JoltDevice,
+    inputs: Option<&[u8]>,
+    outputs: Option<&[u8]>,
+) -> Result<()> {
+    if let Some(guest_panic) = program_io.guest_panic() {
+        return Err(eyre!("proof attests to a guest panic: {}", guest_panic));
+    }
+    if matches!(inputs, Some(inputs) if program_io.inputs != inputs) {
+        return Err(eyre!("proof was generated for different inputs"));
+    }
+    if matches!(outputs, Some(outputs) if program_io.outputs != outputs) {
+        return Err(eyre!("proof attests to different outputs"));
+    }
+    Ok(())
+}
+
+fn print_program_io(program_io: &JoltDevice) {
+    eprintln!("Program IO of the proof:");
+    eprintln!("  inputs:  0x{}", encode_hex(&program_io.inputs));
+    eprintln!("  outputs: 0x{}", encode_hex(&program_io.outputs));
+    eprintln!("  panic:   {}", program_io.panic);
+}
+
+/// Reads a byte argument given either as the path of a file or as a hex string, optionally
+/// prefixed with `0x`.
+pub fn parse_bytes_arg(arg: Option<&str>, name: &str) -> Result<Option<Vec<u8>>> {
+    let Some(arg) = arg else {
+        return Ok(None);
+    };
+    if Path::new(arg).is_file() {
+        return Ok(Some(fs::read(arg)?));
+    }
+    decode_hex(arg)
+        .map(Some)
+        .ok_or_else(|| eyre!("--{} is neither a file nor a hex string: {}", name, arg))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
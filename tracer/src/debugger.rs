@@ -0,0 +1,195 @@
+use std::collections::BTreeSet;
+
+use common::rv_trace::{JoltDevice, MemoryState};
+
+use crate::emulator::{cpu::Xlen, Emulator};
+use crate::{function_symbols, get_xlen, setup_emulator, FunctionSymbol, TracerError};
+
+/// Why [`Debugger::step`] or [`Debugger::resume`] returned control to the caller.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StopReason {
+    /// A single instruction was executed.
+    Step,
+    /// Execution reached a breakpoint. The instruction at the breakpoint has not been
+    /// executed yet.
+    Breakpoint(u64),
+    /// The last instruction accessed the program inputs or outputs, and I/O is watched.
+    Io(MemoryState),
+    /// The guest halted.
+    Terminated,
+    /// [`Debugger::resume`] executed its maximum number of cycles.
+    CycleLimit,
+}
+
+/// Runs a guest one instruction at a time, for inspecting guests that misbehave inside
+/// the zkVM. Execution is identical to [`trace`](crate::trace), so the guest sees the
+/// same inputs and memory layout as when it is proven.
+pub struct Debugger {
+    emulator: Emulator,
+    symbols: Vec<FunctionSymbol>,
+    breakpoints: BTreeSet<u64>,
+    watch_io: bool,
+    cycles: u64,
+}
+
+impl Debugger {
+    pub fn new(
+        elf: &[u8],
+        inputs: &[u8],
+        input_size: u64,
+        output_size: u64,
+    ) -> Result<Self, TracerError> {
+        let emulator = setup_emulator(elf, inputs, input_size, output_size)?;
+        Ok(Self {
+            emulator,
+            symbols: function_symbols(elf)?,
+            breakpoints: BTreeSet::new(),
+            watch_io: false,
+            cycles: 0,
+        })
+    }
+
+    pub fn pc(&self) -> u64 {
+        zero_extend(self.emulator.get_cpu().read_pc())
+    }
+
+    /// Number of instructions executed so far.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Value of register `x{reg}`, zero-extended from XLEN bits.
+    pub fn register(&self, reg: u8) -> u64 {
+        zero_extend(self.emulator.get_cpu().read_register(reg) as u64)
+    }
+
+    /// Reads `len` bytes of guest memory starting at `address`, or `None` if part of the
+    /// range is not mapped. Reading memory does not affect the execution.
+    pub fn read_memory(&self, address: u64, len: u64) -> Option<Vec<u8>> {
+        let mmu = self.emulator.get_cpu().get_mmu();
+        (0..len)
+            .map(|offset| mmu.peek(address.checked_add(offset)?))
+            .collect()
+    }
+
+    /// Disassembly of the instruction at the program counter.
+    pub fn disassemble(&mut self) -> String {
+        self.emulator.get_mut_cpu().disassemble_next_instruction()
+    }
+
+    /// Function symbols of the guest, sorted by address.
+    pub fn symbols(&self) -> &[FunctionSymbol] {
+        &self.symbols
+    }
+
+    /// The function containing `address`, if any.
+    pub fn function_at(&self, address: u64) -> Option<&FunctionSymbol> {
+        let index = self
+            .symbols
+            .partition_point(|symbol| symbol.address <= address);
+        let symbol = &self.symbols[index.checked_sub(1)?];
+        (symbol.size == 0 || address < symbol.address + symbol.size).then_some(symbol)
+    }
+
+    /// The program inputs and the outputs written so far.
+    pub fn device(&self) -> &JoltDevice {
+        &self.emulator.get_cpu().get_mmu().jolt_device
+    }
+
+    /// The bytes the guest printed to its log so far.
+    pub fn log(&self) -> &[u8] {
+        &self.emulator.get_cpu().get_mmu().log
+    }
+
+    pub fn is_terminated(&self) -> bool {
+        self.device().termination
+    }
+
+    /// Adds a breakpoint at `address`, returning `false` if there already was one.
+    pub fn add_breakpoint(&mut self, address: u64) -> bool {
+        self.breakpoints.insert(address)
+    }
+
+    /// Removes the breakpoint at `address`, returning `false` if there was none.
+    pub fn remove_breakpoint(&mut self, address: u64) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = u64> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Whether to stop after instructions that read the inputs or write the outputs of
+    /// the guest, including the panic, termination and panic info addresses.
+    pub fn set_watch_io(&mut self, watch_io: bool) {
+        self.watch_io = watch_io;
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) -> Result<StopReason, TracerError> {
+        if self.is_terminated() {
+            return Ok(StopReason::Terminated);
+        }
+
+        self.emulator.tick();
+        if let Some(err) = self.emulator.get_mut_cpu().take_fault() {
+            return Err(err);
+        }
+        self.cycles += 1;
+
+        let rows: Vec<_> = self
+            .emulator
+            .get_cpu()
+            .tracer
+            .rows
+            .borrow_mut()
+            .drain(..)
+            .collect();
+        if self.is_terminated() {
+            return Ok(StopReason::Terminated);
+        }
+        if self.watch_io {
+            let device = self.device();
+            let io_access = rows
+                .into_iter()
+                .filter_map(|row| row.memory_state)
+                .find(|state| {
+                    let (MemoryState::Read { address, .. } | MemoryState::Write { address, .. }) =
+                        *state;
+                    device.is_input(address)
+                        || device.is_output(address)
+                        || device.is_panic(address)
+                        || device.is_panic_info(address)
+                });
+            if let Some(access) = io_access {
+                return Ok(StopReason::Io(access));
+            }
+        }
+        Ok(StopReason::Step)
+    }
+
+    /// Executes instructions until a breakpoint is reached, a watched access happens or
+    /// the guest halts, executing at most `max_cycles` instructions.
+    pub fn resume(&mut self, max_cycles: u64) -> Result<StopReason, TracerError> {
+        for _ in 0..max_cycles {
+            match self.step()? {
+                StopReason::Step => {
+                    let pc = self.pc();
+                    if self.breakpoints.contains(&pc) {
+                        return Ok(StopReason::Breakpoint(pc));
+                    }
+                }
+                reason => return Ok(reason),
+            }
+        }
+        Ok(StopReason::CycleLimit)
+    }
+}
+
+/// The emulator keeps 32-bit values sign-extended to 64 bits.
+fn zero_extend(value: u64) -> u64 {
+    match get_xlen() {
+        Xlen::Bit32 => value as u32 as u64,
+        Xlen::Bit64 => value,
+    }
+}
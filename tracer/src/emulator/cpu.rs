@@ -7,6 +7,8 @@ use std::rc::Rc;
 use std::str::FromStr;
 
 use crate::trace::Tracer;
+use crate::TracerError;
+use common::precompiles::{keccak_f, sha256_compress};
 use common::rv_trace::*;
 
 use self::fnv::FnvHashMap;
@@ -84,6 +86,11 @@ pub struct Cpu {
     decode_cache: DecodeCache,
     unsigned_data_mask: u64,
     pub tracer: Rc<Tracer>,
+    /// Error that stopped execution, e.g. an unsupported instruction. Once set the
+    /// program cannot be traced any further.
+    fault: Option<TracerError>,
+    /// Custom instructions bound to encodings of the custom-0 and custom-1 opcodes.
+    custom_instructions: Vec<CustomInstruction>,
 }
 
 #[derive(Clone)]
@@ -245,6 +252,8 @@ impl Cpu {
             decode_cache: DecodeCache::new(),
             unsigned_data_mask: 0xffffffffffffffff,
             tracer,
+            fault: None,
+            custom_instructions: Vec::new(),
         };
         cpu.x[0xb] = 0x1020; // I don't know why but Linux boot seems to require this initialization
         cpu.write_csr_raw(CSR_MISA_ADDRESS, 0x800000008014312f);
@@ -292,9 +301,18 @@ impl Cpu {
     /// Runs program one cycle. Fetch, decode, and execution are completed in a cycle so far.
     pub fn tick(&mut self) {
         let instruction_address = self.pc;
-        match self.tick_operate() {
-            Ok(()) => {}
-            Err(e) => self.handle_exception(e, instruction_address),
+        let result = self.tick_operate();
+        if let Some(address) = self.mmu.take_fault() {
+            self.fault = Some(TracerError::MemoryOutOfBounds {
+                pc: instruction_address,
+                address,
+            });
+        }
+        if self.fault.is_some() {
+            return;
+        }
+        if let Err(e) = result {
+            self.handle_exception(e, instruction_address);
         }
         self.mmu.tick(&mut self.csr[CSR_MIP_ADDRESS as usize]);
         self.handle_interrupt(self.pc);
@@ -327,14 +345,16 @@ impl Cpu {
             }
             false => {
                 self.pc = self.pc.wrapping_add(2); // 16-bit length compressed instruction
-                self.uncompress(original_word & 0xffff)
+                Self::uncompress(original_word & 0xffff, &self.xlen)
             }
         };
 
         match self.decode(word).cloned() {
             Ok(inst) => {
                 // setup trace
-                let trace_inst = inst.trace.unwrap()(&inst, &self.xlen, word, instruction_address);
+                let mut trace_inst =
+                    inst.trace.unwrap()(&inst, &self.xlen, word, instruction_address);
+                trace_inst.is_compressed = (original_word & 0x3) != 0x3;
                 self.tracer.start_instruction(trace_inst);
                 self.tracer.capture_pre_state(self.x, &self.xlen);
 
@@ -349,10 +369,11 @@ impl Cpu {
                 result
             }
             Err(()) => {
-                panic!(
-                    "Unknown instruction PC:{:x} WORD:{:x}",
-                    instruction_address, original_word
-                );
+                self.fault = Some(TracerError::UnsupportedInstruction {
+                    pc: instruction_address,
+                    word: original_word,
+                });
+                Ok(())
             }
         }
     }
@@ -887,8 +908,10 @@ impl Cpu {
         }
     }
 
+    /// Expands a 16-bit compressed (RVC) instruction into its 32-bit equivalent, or returns
+    /// 0xffffffff if `halfword` is not a valid compressed instruction.
     // @TODO: Optimize
-    fn uncompress(&self, halfword: u32) -> u32 {
+    pub fn uncompress(halfword: u32, xlen: &Xlen) -> u32 {
         let op = halfword & 0x3; // [1:0]
         let funct3 = (halfword >> 13) & 0x7; // [15:13]
 
@@ -1015,7 +1038,11 @@ impl Cpu {
                         // r == 0 and imm != 0 is HINTs
                     }
                     1 => {
-                        // @TODO: Support C.JAL in 32-bit mode
+                        if let Xlen::Bit32 = xlen {
+                            // C.JAL
+                            // jal x1, offset, encoded like C.J apart from funct3
+                            return Self::uncompress((halfword & 0x1fff) | 0xa000, xlen) | (1 << 7);
+                        }
                         // C.ADDIW
                         // addiw r, r, imm
                         let r = (halfword >> 7) & 0x1f;
@@ -1428,7 +1455,7 @@ impl Cpu {
             true => original_word,
             false => {
                 original_word &= 0xffff;
-                self.uncompress(original_word)
+                Self::uncompress(original_word, &self.xlen)
             }
         };
 
@@ -1451,6 +1478,96 @@ impl Cpu {
         s
     }
 
+    /// Returns the error that stopped execution, if any, clearing it.
+    pub fn take_fault(&mut self) -> Option<TracerError> {
+        self.fault.take()
+    }
+
+    /// Binds custom instructions to their encodings, replacing any bound before. Executing
+    /// an encoding of the custom-0 or custom-1 opcode that is not bound is an error.
+    pub fn set_custom_instructions(&mut self, custom_instructions: &[CustomInstruction]) {
+        self.custom_instructions = custom_instructions.to_vec();
+    }
+
+    fn execute_custom(&mut self, word: u32, address: u64) -> Result<(), Trap> {
+        let custom_instruction = self
+            .custom_instructions
+            .iter()
+            .find(|instruction| word & 0xfe00707f == instruction.encoding())
+            .copied();
+        match custom_instruction {
+            Some(instruction) => {
+                let f = parse_format_r(word);
+                let rs1 = self.unsigned_data(self.x[f.rs1]);
+                let rs2 = self.unsigned_data(self.x[f.rs2]);
+                self.x[f.rd] = self.sign_extend((instruction.execute)(rs1, rs2) as i64);
+            }
+            None => {
+                self.fault = Some(TracerError::UnsupportedInstruction { pc: address, word });
+            }
+        }
+        Ok(())
+    }
+
+    /// Compresses the SHA-256 state of eight words at rs1 with the block of sixteen
+    /// big-endian message words at rs2, in place.
+    fn execute_sha256_compress(&mut self, word: u32) -> Result<(), Trap> {
+        let f = parse_format_r(word);
+        let state_address = self.x[f.rs1] as u64;
+        let block_address = self.x[f.rs2] as u64;
+
+        let mut state = [0u32; 8];
+        for (i, word) in state.iter_mut().enumerate() {
+            *word = self
+                .mmu
+                .load_word(state_address.wrapping_add(4 * i as u64))?;
+        }
+        let mut block = [0u32; 16];
+        for (i, word) in block.iter_mut().enumerate() {
+            *word = self
+                .mmu
+                .load_word(block_address.wrapping_add(4 * i as u64))?;
+        }
+
+        sha256_compress(&mut state, &block);
+
+        for (i, word) in state.iter().enumerate() {
+            self.mmu
+                .store_word(state_address.wrapping_add(4 * i as u64), *word)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the Keccak-f[1600] permutation to the 25 little-endian 64-bit lanes at rs1,
+    /// in place.
+    fn execute_keccak_f(&mut self, word: u32) -> Result<(), Trap> {
+        let f = parse_format_r(word);
+        let state_address = self.x[f.rs1] as u64;
+
+        let mut state = [0u64; 25];
+        for (i, lane) in state.iter_mut().enumerate() {
+            let address = state_address.wrapping_add(8 * i as u64);
+            let low = self.mmu.load_word(address)?;
+            let high = self.mmu.load_word(address.wrapping_add(4))?;
+            *lane = (low as u64) | ((high as u64) << 32);
+        }
+
+        keccak_f(&mut state);
+
+        for (i, lane) in state.iter().enumerate() {
+            let address = state_address.wrapping_add(8 * i as u64);
+            self.mmu.store_word(address, *lane as u32)?;
+            self.mmu
+                .store_word(address.wrapping_add(4), (*lane >> 32) as u32)?;
+        }
+        Ok(())
+    }
+
+    /// Returns immutable `Mmu`
+    pub fn get_mmu(&self) -> &Mmu {
+        &self.mmu
+    }
+
     /// Returns mutable `Mmu`
     pub fn get_mut_mmu(&mut self) -> &mut Mmu {
         &mut self.mmu
@@ -1473,6 +1590,30 @@ pub struct Instruction {
         Option<fn(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction>,
 }
 
+/// An instruction the host binds to an encoding of the custom-0 or custom-1 opcode. It is
+/// an R-type instruction that writes `execute(rs1, rs2)` to rd, where register values are
+/// unsigned and XLEN bits wide.
+#[derive(Debug, Clone, Copy)]
+pub struct CustomInstruction {
+    /// `RV32IM::CUSTOM_0` or `RV32IM::CUSTOM_1`
+    pub opcode: RV32IM,
+    pub funct3: u32,
+    pub funct7: u32,
+    pub execute: fn(u64, u64) -> u64,
+}
+
+impl CustomInstruction {
+    /// The opcode, funct3 and funct7 bits of the instruction's encoding.
+    fn encoding(&self) -> u32 {
+        let opcode = match self.opcode {
+            RV32IM::CUSTOM_0 => 0x0b,
+            RV32IM::CUSTOM_1 => 0x2b,
+            _ => panic!("{:?} is not a custom opcode", self.opcode),
+        };
+        (self.funct7 << 25) | (self.funct3 << 12) | opcode
+    }
+}
+
 struct FormatB {
     rs1: usize,
     rs2: usize,
@@ -1755,7 +1896,6 @@ fn parse_format_u(word: u32) -> FormatU {
 }
 
 fn dump_format_u(cpu: &mut Cpu, word: u32, _address: u64, evaluate: bool) -> String {
-    println!("f format: {:x}", word);
     let f = parse_format_u(word);
     let mut s = String::new();
     s += &format!("{}", get_register_name(f.rd));
@@ -1848,6 +1988,16 @@ fn trace_r(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: Some(normalize_register(f.rs2)),
         rd: Some(normalize_register(f.rd)),
         virtual_sequence_remaining: None,
+        is_compressed: false,
+    }
+}
+
+fn trace_custom(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let funct3 = (word >> 12) & 0x7;
+    let funct7 = word >> 25;
+    ELFInstruction {
+        imm: Some((funct7 << 3) | funct3),
+        ..trace_r(inst, xlen, word, address)
     }
 }
 
@@ -1861,6 +2011,7 @@ fn trace_i(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: None,
         rd: Some(normalize_register(f.rd)),
         virtual_sequence_remaining: None,
+        is_compressed: false,
     }
 }
 
@@ -1874,6 +2025,7 @@ fn trace_s(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: Some(normalize_register(f.rs2)),
         rd: None,
         virtual_sequence_remaining: None,
+        is_compressed: false,
     }
 }
 
@@ -1887,6 +2039,7 @@ fn trace_b(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: Some(normalize_register(f.rs2)),
         rd: None,
         virtual_sequence_remaining: None,
+        is_compressed: false,
     }
 }
 
@@ -1900,6 +2053,7 @@ fn trace_u(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: None,
         rd: Some(normalize_register(f.rd)),
         virtual_sequence_remaining: None,
+        is_compressed: false,
     }
 }
 
@@ -1914,10 +2068,11 @@ fn trace_j(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: None,
         rd: Some(normalize_register(f.rd)),
         virtual_sequence_remaining: None,
+        is_compressed: false,
     }
 }
 
-const INSTRUCTION_NUM: usize = 116;
+const INSTRUCTION_NUM: usize = 124;
 
 // @TODO: Reorder in often used order as
 pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
@@ -2013,7 +2168,7 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xf800707f,
@@ -2059,7 +2214,28 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xf800707f,
+        data: 0xa000202f,
+        name: "AMOMAX.W",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let tmp = match cpu.mmu.load_word(cpu.x[f.rs1] as u64) {
+                Ok(data) => data as i32,
+                Err(e) => return Err(e),
+            };
+            let value = (cpu.x[f.rs2] as i32).max(tmp);
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, value as u32) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
+            };
+            cpu.x[f.rd] = tmp as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xf800707f,
@@ -2107,7 +2283,49 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xf800707f,
+        data: 0x8000202f,
+        name: "AMOMIN.W",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let tmp = match cpu.mmu.load_word(cpu.x[f.rs1] as u64) {
+                Ok(data) => data as i32,
+                Err(e) => return Err(e),
+            };
+            let value = (cpu.x[f.rs2] as i32).min(tmp);
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, value as u32) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
+            };
+            cpu.x[f.rd] = tmp as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xf800707f,
+        data: 0xc000202f,
+        name: "AMOMINU.W",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let tmp = match cpu.mmu.load_word(cpu.x[f.rs1] as u64) {
+                Ok(data) => data as i32,
+                Err(e) => return Err(e),
+            };
+            let value = (cpu.x[f.rs2] as u32).min(tmp as u32) as i32;
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, value as u32) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
+            };
+            cpu.x[f.rd] = tmp as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xf800707f,
@@ -2153,7 +2371,7 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xf800707f,
@@ -2196,7 +2414,28 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xf800707f,
+        data: 0x2000202f,
+        name: "AMOXOR.W",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let tmp = match cpu.mmu.load_word(cpu.x[f.rs1] as u64) {
+                Ok(data) => data as i32,
+                Err(e) => return Err(e),
+            };
+            let value = cpu.x[f.rs2] as i32 ^ tmp;
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, value as u32) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
+            };
+            cpu.x[f.rd] = tmp as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xfe00707f,
@@ -3076,7 +3315,7 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0x0000007f,
@@ -3372,21 +3611,28 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         name: "SC.W",
         operation: |cpu, word, _address| {
             let f = parse_format_r(word);
-            // @TODO: Implement properly
-            cpu.x[f.rd] = match cpu.is_reservation_set && cpu.reservation == (cpu.x[f.rs1] as u64) {
-                true => match cpu.mmu.store_word(cpu.x[f.rs1] as u64, cpu.x[f.rs2] as u32) {
-                    Ok(()) => {
-                        cpu.is_reservation_set = false;
-                        0
-                    }
-                    Err(e) => return Err(e),
-                },
-                false => 1,
-            };
+            let address = cpu.x[f.rs1] as u64;
+            // The word is loaded even if the store fails, as Jolt proves SC.W as a load and
+            // a store of either rs2 or the loaded word.
+            cpu.mmu.load_word(address)?;
+            if cpu.is_reservation_set && cpu.reservation == address {
+                cpu.mmu.store_word(address, cpu.x[f.rs2] as u32)?;
+                cpu.x[f.rd] = 0;
+            } else {
+                // The proof compares rs1 with the reserved address, zero if there is none.
+                let reservation = match cpu.is_reservation_set {
+                    true => cpu.reservation as u32 as u64,
+                    false => 0,
+                };
+                cpu.tracer.push_advice(reservation);
+                cpu.x[f.rd] = 1;
+            }
+            // SC.W invalidates the reservation whether or not it succeeds.
+            cpu.is_reservation_set = false;
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0x0000707f,
@@ -3766,6 +4012,38 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_i,
         trace: Some(trace_i),
     },
+    Instruction {
+        mask: 0x0000007f,
+        data: 0x0000000b,
+        name: "CUSTOM_0",
+        operation: |cpu, word, address| cpu.execute_custom(word, address),
+        disassemble: dump_format_r,
+        trace: Some(trace_custom),
+    },
+    Instruction {
+        mask: 0x0000007f,
+        data: 0x0000002b,
+        name: "CUSTOM_1",
+        operation: |cpu, word, address| cpu.execute_custom(word, address),
+        disassemble: dump_format_r,
+        trace: Some(trace_custom),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0000005b,
+        name: "SHA256_COMPRESS",
+        operation: |cpu, word, _address| cpu.execute_sha256_compress(word),
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0000105b,
+        name: "KECCAK_F",
+        operation: |cpu, word, _address| cpu.execute_keccak_f(word),
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
 ];
 
 /// The number of results [`DecodeCache`](struct.DecodeCache.html) holds.
@@ -3956,6 +4234,7 @@ mod test_cpu {
     use super::*;
     use crate::emulator::mmu::DRAM_BASE;
     use crate::emulator::terminal::DummyTerminal;
+    use common::constants::LOG_ADDRESS;
 
     fn create_cpu() -> Cpu {
         Cpu::new(Box::new(DummyTerminal::new()))
@@ -4056,6 +4335,267 @@ mod test_cpu {
         assert_eq!(8, cpu.read_register(8));
     }
 
+    #[test]
+    fn tick_unsupported_instruction() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(4);
+        cpu.update_pc(DRAM_BASE);
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0xffffffff) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+
+        cpu.tick();
+
+        match cpu.take_fault() {
+            Some(TracerError::UnsupportedInstruction { pc, word }) => {
+                assert_eq!(DRAM_BASE, pc);
+                assert_eq!(0xffffffff, word);
+            }
+            _ => panic!("Expected an unsupported instruction fault"),
+        };
+    }
+
+    #[test]
+    fn tick_custom_instruction() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        cpu.set_custom_instructions(&[CustomInstruction {
+            opcode: RV32IM::CUSTOM_0,
+            funct3: 1,
+            funct7: 0,
+            execute: |x, y| x & !y,
+        }]);
+        // Write ".insn r 0x0b, 1, 0, a0, a1, a2" instruction
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00c5950b) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        // Write ".insn r 0x0b, 2, 0, a0, a1, a2" instruction, which is not bound
+        match cpu.get_mut_mmu().store_word(DRAM_BASE + 4, 0x00c5a50b) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        cpu.x[11] = 0b1100;
+        cpu.x[12] = 0b1010;
+
+        cpu.tick();
+
+        assert!(cpu.take_fault().is_none());
+        assert_eq!(0b0100, cpu.read_register(10));
+
+        cpu.tick();
+
+        match cpu.take_fault() {
+            Some(TracerError::UnsupportedInstruction { pc, word }) => {
+                assert_eq!(DRAM_BASE + 4, pc);
+                assert_eq!(0x00c5a50b, word);
+            }
+            _ => panic!("Expected an unsupported instruction fault"),
+        };
+    }
+
+    #[test]
+    fn tick_sha256_compress() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(256);
+        cpu.update_pc(DRAM_BASE);
+        // Write ".insn r 0x5b, 0, 0, x0, a0, a1" instruction
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00b5005b) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        let state_address = DRAM_BASE + 64;
+        let block_address = DRAM_BASE + 128;
+        let initial_state: [u32; 8] = [
+            0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+            0x5be0cd19,
+        ];
+        // The padded block of the message "abc"
+        let mut block = [0u32; 16];
+        block[0] = 0x61626380;
+        block[15] = 0x18;
+        let words = initial_state
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (state_address, i, word));
+        let words = words.chain(
+            block
+                .iter()
+                .enumerate()
+                .map(|(i, word)| (block_address, i, word)),
+        );
+        for (address, i, word) in words {
+            match cpu.get_mut_mmu().store_word(address + 4 * i as u64, *word) {
+                Ok(()) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        cpu.x[10] = state_address as i64;
+        cpu.x[11] = block_address as i64;
+
+        cpu.tick();
+
+        assert!(cpu.take_fault().is_none());
+        let digest: [u32; 8] = [
+            0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3, 0x96177a9c, 0xb410ff61,
+            0xf20015ad,
+        ];
+        for (i, word) in digest.iter().enumerate() {
+            assert_eq!(
+                *word,
+                cpu.get_mut_mmu()
+                    .load_word_raw(state_address + 4 * i as u64)
+            );
+        }
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+    }
+
+    #[test]
+    fn tick_keccak_f() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(512);
+        cpu.update_pc(DRAM_BASE);
+        // Write ".insn r 0x5b, 1, 0, x0, a0, x0" instruction
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x0005105b) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        // The state is all zero, since memory is zero initialized
+        let state_address = DRAM_BASE + 64;
+        cpu.x[10] = state_address as i64;
+
+        cpu.tick();
+
+        assert!(cpu.take_fault().is_none());
+        // The first lanes of Keccak-f[1600] applied to the zero state
+        let lanes: [u64; 4] = [
+            0xf1258f7940e1dde7,
+            0x84d5ccf933c0478a,
+            0xd598261ea65aa9ee,
+            0xbd1547306f80494d,
+        ];
+        for (i, lane) in lanes.iter().enumerate() {
+            let address = state_address + 8 * i as u64;
+            assert_eq!(*lane as u32, cpu.get_mut_mmu().load_word_raw(address));
+            assert_eq!(
+                (*lane >> 32) as u32,
+                cpu.get_mut_mmu().load_word_raw(address + 4)
+            );
+        }
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+    }
+
+    #[test]
+    fn tick_sc_w() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(128);
+        cpu.update_pc(DRAM_BASE);
+        // Write "sc.w a2, a1, (a0)", "lr.w a3, (a0)", "sc.w a2, a1, (a0)" and
+        // "sc.w a2, a1, (a0)" instructions
+        for (i, word) in [0x18b5262f, 0x100526af, 0x18b5262f, 0x18b5262f]
+            .iter()
+            .enumerate()
+        {
+            match cpu
+                .get_mut_mmu()
+                .store_word(DRAM_BASE + 4 * i as u64, *word)
+            {
+                Ok(()) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        let address = DRAM_BASE + 64;
+        match cpu.get_mut_mmu().store_word(address, 0x1234) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        cpu.x[10] = address as i64;
+        cpu.x[11] = 0x5678;
+
+        // No reservation is set, so the store fails
+        cpu.tick();
+        assert_eq!(1, cpu.read_register(12));
+        assert_eq!(0x1234, cpu.get_mut_mmu().load_word_raw(address));
+        {
+            let rows = cpu.tracer.rows.borrow();
+            let row = rows.last().unwrap();
+            assert_eq!(
+                Some(MemoryState::Read {
+                    address,
+                    value: 0x1234
+                }),
+                row.memory_state
+            );
+            assert_eq!(Some(0), row.advice_value);
+        }
+
+        cpu.tick();
+        assert_eq!(0x1234, cpu.read_register(13));
+
+        cpu.tick();
+        assert_eq!(0, cpu.read_register(12));
+        assert_eq!(0x5678, cpu.get_mut_mmu().load_word_raw(address));
+
+        // The successful SC.W invalidated the reservation
+        cpu.x[11] = 0x9abc;
+        cpu.tick();
+        assert_eq!(1, cpu.read_register(12));
+        assert_eq!(0x5678, cpu.get_mut_mmu().load_word_raw(address));
+
+        assert!(cpu.take_fault().is_none());
+        assert_eq!(DRAM_BASE + 16, cpu.read_pc());
+    }
+
+    #[test]
+    fn tick_out_of_bounds_store() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        // Write "sw x0, 8(x0)" instruction, storing below DRAM_BASE
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00002423) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+
+        cpu.tick();
+
+        match cpu.take_fault() {
+            Some(TracerError::MemoryOutOfBounds { pc, address }) => {
+                assert_eq!(DRAM_BASE, pc);
+                assert_eq!(8, address);
+            }
+            _ => panic!("Expected an out-of-bounds fault"),
+        };
+    }
+
+    #[test]
+    fn tick_store_log() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        // Write "sb a1, 0(a0)" instruction
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00b50023) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        cpu.x[10] = LOG_ADDRESS as i64;
+        cpu.x[11] = b'j' as i64;
+
+        cpu.tick();
+
+        assert!(cpu.take_fault().is_none());
+        let mmu = cpu.get_mmu();
+        assert_eq!(b"j", mmu.log.as_slice());
+        assert!(mmu.jolt_device.outputs.is_empty());
+        assert!(mmu.jolt_device.panic_info.is_empty());
+        assert!(!mmu.jolt_device.panic);
+        assert!(!mmu.jolt_device.termination);
+        assert_eq!(Some(b'j'), mmu.peek(LOG_ADDRESS));
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+    }
+
     #[test]
     fn tick_operate() {
         let mut cpu = create_cpu();
@@ -4077,7 +4617,31 @@ mod test_cpu {
         assert_eq!(DRAM_BASE + 4, cpu.read_pc());
         // "addi a0, a0, a12" instruction writes 12 to a0 register.
         assert_eq!(12, cpu.read_register(10));
-        // @TODO: Test compressed instruction operation
+    }
+
+    #[test]
+    fn tick_operate_compressed() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(4);
+        cpu.update_pc(DRAM_BASE);
+        // write compressed "c.addi a0, 4" instruction
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x0511) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        match cpu.tick_operate() {
+            Ok(()) => {}
+            Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+        };
+        // .tick_operate() increments the program counter by 2 for
+        // compressed instruction.
+        assert_eq!(DRAM_BASE + 2, cpu.read_pc());
+        assert_eq!(4, cpu.read_register(10));
+        // The instruction is traced as its 32-bit equivalent at its own address
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(RV32IM::ADDI, rows[0].instruction.opcode);
+        assert_eq!(DRAM_BASE, rows[0].instruction.address);
+        assert!(rows[0].instruction.is_compressed);
     }
 
     #[test]
@@ -4129,10 +4693,12 @@ mod test_cpu {
         let mut cpu = create_cpu();
         // .uncompress() doesn't directly return an instruction but
         // it returns uncompressed word. Then you need to call .decode().
-        match cpu.decode(cpu.uncompress(0x20)) {
+        match cpu.decode(Cpu::uncompress(0x20, &cpu.xlen)) {
             Ok(inst) => assert_eq!(inst.name, "ADDI"),
             Err(_e) => panic!("Failed to decode"),
         };
+        // c.jal only exists in 32-bit mode, where it expands to "jal x1, 4"
+        assert_eq!(0x004000ef, Cpu::uncompress(0x2011, &Xlen::Bit32));
         // @TODO: Should I test all compressed instructions?
     }
 
@@ -46,7 +46,7 @@ pub struct SectionHeader {
     pub sh_addr: u64,
     pub sh_offset: u64,
     pub sh_size: u64,
-    _sh_link: u32,
+    pub sh_link: u32,
     _sh_info: u32,
     _sh_addralign: u64,
     _sh_entsize: u64,
@@ -59,7 +59,7 @@ pub struct SymbolEntry {
     _st_other: u8,
     _st_shndx: u16,
     st_value: u64,
-    _st_size: u64,
+    st_size: u64,
 }
 
 /// ELF file analyzer
@@ -482,7 +482,7 @@ impl ElfAnalyzer {
                 sh_addr,
                 sh_offset,
                 sh_size,
-                _sh_link: sh_link,
+                sh_link,
                 _sh_info: sh_info,
                 _sh_addralign: sh_addralign,
                 _sh_entsize: sh_entsize,
@@ -582,7 +582,7 @@ impl ElfAnalyzer {
                     _st_other,
                     _st_shndx,
                     st_value,
-                    _st_size,
+                    st_size: _st_size,
                 });
             }
         }
@@ -646,6 +646,39 @@ impl ElfAnalyzer {
         map
     }
 
+    /// Reads function symbols from symbol table sections as (name, address, size)
+    /// tuples. The names are looked up in the string table linked to each section.
+    ///
+    /// # Arguments
+    /// * `header`
+    /// * `section_headers` All section headers
+    pub fn read_function_symbols(
+        &self,
+        header: &Header,
+        section_headers: &[SectionHeader],
+    ) -> Vec<(String, u64, u64)> {
+        let mut symbols = Vec::new();
+        for section_header in section_headers.iter().filter(|h| h.sh_type == 2) {
+            let Some(string_table_section_header) =
+                section_headers.get(section_header.sh_link as usize)
+            else {
+                continue;
+            };
+            let entries = self.read_symbol_entries(header, &vec![section_header]);
+            for entry in entries {
+                // STT_FUNC
+                if (entry.st_info & 0xf) != 0x2 {
+                    continue;
+                }
+                let symbol = self.read_strings(string_table_section_header, entry.st_name as u64);
+                if !symbol.is_empty() {
+                    symbols.push((symbol, entry.st_value, entry.st_size));
+                }
+            }
+        }
+        symbols
+    }
+
     /// Finds a program data section whose name is .tohost. If found this method
     /// returns an address of the section.
     ///
@@ -163,6 +163,6 @@ impl Memory {
     /// # Arguments
     /// * `address`
     pub fn validate_address(&self, address: u64) -> bool {
-        (address as usize) < self.data.len()
+        ((address >> 3) as usize) < self.data.len()
     }
 }
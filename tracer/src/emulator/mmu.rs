@@ -40,7 +40,11 @@ pub struct Mmu {
     uart: Uart,
 
     pub jolt_device: JoltDevice,
+    /// Bytes the guest stored to the log address, kept apart from the program IO.
+    pub log: Vec<u8>,
     tracer: Rc<Tracer>,
+    /// Address of an out-of-bounds access made during the current cycle.
+    fault: Option<u64>,
 
     /// Address translation can be affected `mstatus` (MPRV, MPP in machine mode)
     /// then `Mmu` has copy of it.
@@ -113,7 +117,9 @@ impl Mmu {
             clint: Clint::new(),
             uart: Uart::new(terminal),
             jolt_device: JoltDevice::new(0, 0),
+            log: Vec::new(),
             tracer,
+            fault: None,
             mstatus: 0,
             page_cache_enabled: false,
             fetch_page_cache: FnvHashMap::default(),
@@ -252,6 +258,10 @@ impl Mmu {
     /// * `v_address` Virtual address
     pub fn fetch_word(&mut self, v_address: u64) -> Result<u32, Trap> {
         let width = 4;
+        let effective_address = self.get_effective_address(v_address);
+        if !self.is_dram(effective_address, width) {
+            return Err(self.access_fault(effective_address, TrapType::InstructionAccessFault));
+        }
         match (v_address & 0xfff) <= (0x1000 - width) {
             true => {
                 // Fast path. All bytes fetched are in the same page so
@@ -285,7 +295,7 @@ impl Mmu {
     /// * `v_address` Virtual address
     pub fn load(&mut self, v_address: u64) -> Result<u8, Trap> {
         let effective_address = self.get_effective_address(v_address);
-        self.trace_load(effective_address, 1);
+        self.trace_load(effective_address, 1)?;
         match self.translate_address(effective_address, &MemoryAccessType::Read) {
             Ok(p_address) => Ok(self.load_raw(p_address)),
             Err(()) => Err(Trap {
@@ -345,7 +355,7 @@ impl Mmu {
     /// * `v_address` Virtual address
     pub fn load_halfword(&mut self, v_address: u64) -> Result<u16, Trap> {
         let effective_address = self.get_effective_address(v_address);
-        self.trace_load(effective_address, 2);
+        self.trace_load(effective_address, 2)?;
         match self.load_bytes(v_address, 2) {
             Ok(data) => Ok(data as u16),
             Err(e) => Err(e),
@@ -359,7 +369,7 @@ impl Mmu {
     /// * `v_address` Virtual address
     pub fn load_word(&mut self, v_address: u64) -> Result<u32, Trap> {
         let effective_address = self.get_effective_address(v_address);
-        self.trace_load(effective_address, 4);
+        self.trace_load(effective_address, 4)?;
         match self.load_bytes(v_address, 4) {
             Ok(data) => Ok(data as u32),
             Err(e) => Err(e),
@@ -373,7 +383,7 @@ impl Mmu {
     /// * `v_address` Virtual address
     pub fn load_doubleword(&mut self, v_address: u64) -> Result<u64, Trap> {
         let effective_address = self.get_effective_address(v_address);
-        self.trace_load(effective_address, 8);
+        self.trace_load(effective_address, 8)?;
         match self.load_bytes(v_address, 8) {
             Ok(data) => Ok(data),
             Err(e) => Err(e),
@@ -388,7 +398,7 @@ impl Mmu {
     /// * `value`
     pub fn store(&mut self, v_address: u64, value: u8) -> Result<(), Trap> {
         let effective_address = self.get_effective_address(v_address);
-        self.trace_store(effective_address, value as u64);
+        self.trace_store(effective_address, value as u64, 1)?;
         match self.translate_address(v_address, &MemoryAccessType::Write) {
             Ok(p_address) => {
                 self.store_raw(p_address, value);
@@ -453,7 +463,7 @@ impl Mmu {
     /// * `value` data written
     pub fn store_halfword(&mut self, v_address: u64, value: u16) -> Result<(), Trap> {
         let effective_address = self.get_effective_address(v_address);
-        self.trace_store(effective_address, value as u64);
+        self.trace_store(effective_address, value as u64, 2)?;
         self.store_bytes(v_address, value as u64, 2)
     }
 
@@ -465,7 +475,7 @@ impl Mmu {
     /// * `value` data written
     pub fn store_word(&mut self, v_address: u64, value: u32) -> Result<(), Trap> {
         let effective_address = self.get_effective_address(v_address);
-        self.trace_store(effective_address, value as u64);
+        self.trace_store(effective_address, value as u64, 4)?;
         self.store_bytes(v_address, value as u64, 4)
     }
 
@@ -477,7 +487,7 @@ impl Mmu {
     /// * `value` data written
     pub fn store_doubleword(&mut self, v_address: u64, value: u64) -> Result<(), Trap> {
         let effective_address = self.get_effective_address(v_address);
-        self.trace_store(effective_address, value);
+        self.trace_store(effective_address, value, 8)?;
         self.store_bytes(v_address, value, 8)
     }
 
@@ -511,7 +521,64 @@ impl Mmu {
         }
     }
 
-    fn trace_load(&mut self, effective_address: u64, bytes: u64) {
+    /// Returns `true` if the `bytes` bytes starting at `effective_address` lie in main
+    /// memory.
+    fn is_dram(&self, effective_address: u64, bytes: u64) -> bool {
+        effective_address >= DRAM_BASE
+            && effective_address
+                .checked_add(bytes - 1)
+                .is_some_and(|last_address| self.memory.validate_address(last_address))
+    }
+
+    /// Records an access to an address that is neither main memory nor a Jolt device,
+    /// so that the tracer can stop with an error rather than the emulator panicking.
+    fn access_fault(&mut self, effective_address: u64, trap_type: TrapType) -> Trap {
+        self.fault = Some(effective_address);
+        Trap {
+            trap_type,
+            value: effective_address,
+        }
+    }
+
+    /// Returns the address of the last out-of-bounds access, if any, clearing it.
+    pub fn take_fault(&mut self) -> Option<u64> {
+        self.fault.take()
+    }
+
+    /// Reads a byte of main memory or of the program I/O without recording the access in
+    /// the trace, for inspecting the guest from the outside. The log address reads as the
+    /// last byte logged. Returns `None` if nothing is mapped at `effective_address`.
+    pub fn peek(&self, effective_address: u64) -> Option<u8> {
+        if self.is_dram(effective_address, 1) {
+            Some(self.memory.read_byte(effective_address))
+        } else if self.jolt_device.is_input(effective_address) {
+            Some(self.jolt_device.load(effective_address))
+        } else if self.jolt_device.is_output(effective_address) {
+            let index = effective_address - self.jolt_device.memory_layout.output_start;
+            Some(
+                self.jolt_device
+                    .outputs
+                    .get(index as usize)
+                    .copied()
+                    .unwrap_or(0),
+            )
+        } else if self.jolt_device.is_panic_info(effective_address) {
+            let index = effective_address - self.jolt_device.memory_layout.panic_info_start;
+            Some(
+                self.jolt_device
+                    .panic_info
+                    .get(index as usize)
+                    .copied()
+                    .unwrap_or(0),
+            )
+        } else if self.jolt_device.is_log(effective_address) {
+            Some(self.log.last().copied().unwrap_or(0))
+        } else {
+            None
+        }
+    }
+
+    fn trace_load(&mut self, effective_address: u64, bytes: u64) -> Result<(), Trap> {
         if effective_address < DRAM_BASE {
             if self.jolt_device.is_input(effective_address) {
                 let mut value_bytes = [0u8; 8];
@@ -524,9 +591,9 @@ impl Mmu {
                     value,
                 });
             } else {
-                panic!("Unknown memory mapping {:X}.", effective_address);
+                return Err(self.access_fault(effective_address, TrapType::LoadAccessFault));
             }
-        } else {
+        } else if self.is_dram(effective_address, bytes) {
             let mut value_bytes = [0u8; 8];
             for i in 0..bytes {
                 value_bytes[i as usize] = self.memory.read_byte(effective_address + i);
@@ -536,27 +603,36 @@ impl Mmu {
                 address: effective_address,
                 value,
             });
+        } else {
+            return Err(self.access_fault(effective_address, TrapType::LoadAccessFault));
         }
+        Ok(())
     }
 
-    fn trace_store(&mut self, effective_address: u64, value: u64) {
+    fn trace_store(&mut self, effective_address: u64, value: u64, bytes: u64) -> Result<(), Trap> {
         if effective_address < DRAM_BASE {
             if self.jolt_device.is_output(effective_address)
                 || self.jolt_device.is_panic(effective_address)
+                || self.jolt_device.is_termination(effective_address)
+                || self.jolt_device.is_panic_info(effective_address)
+                || self.jolt_device.is_log(effective_address)
             {
                 self.tracer.push_memory(MemoryState::Write {
                     address: effective_address,
                     post_value: value,
                 });
             } else {
-                panic!("Unknown memory mapping {:X}.", effective_address);
+                return Err(self.access_fault(effective_address, TrapType::StoreAccessFault));
             }
-        } else {
+        } else if self.is_dram(effective_address, bytes) {
             self.tracer.push_memory(MemoryState::Write {
                 address: effective_address,
                 post_value: value,
             });
+        } else {
+            return Err(self.access_fault(effective_address, TrapType::StoreAccessFault));
         }
+        Ok(())
     }
 
     /// Loads two bytes from main memory or peripheral devices depending on
@@ -644,8 +720,12 @@ impl Mmu {
                 _ => {
                     if self.jolt_device.is_output(effective_address)
                         || self.jolt_device.is_panic(effective_address)
+                        || self.jolt_device.is_termination(effective_address)
+                        || self.jolt_device.is_panic_info(effective_address)
                     {
                         self.jolt_device.store(effective_address, value);
+                    } else if self.jolt_device.is_log(effective_address) {
+                        self.log.push(value);
                     } else {
                         panic!("Unknown memory mapping {:X}.", effective_address);
                     }
@@ -1033,7 +1113,7 @@ impl MemoryWrapper {
         self.memory.init(capacity);
     }
 
-    pub fn read_byte(&mut self, p_address: u64) -> u8 {
+    pub fn read_byte(&self, p_address: u64) -> u8 {
         debug_assert!(
             p_address >= DRAM_BASE,
             "Memory address must equals to or bigger than DRAM_BASE. {:X}",
@@ -17,6 +17,7 @@ pub mod terminal;
 use self::cpu::{Cpu, Xlen};
 use self::elf_analyzer::ElfAnalyzer;
 use self::terminal::Terminal;
+use crate::TracerError;
 
 /// RISC-V emulator. It emulates RISC-V CPU and peripheral devices.
 ///
@@ -135,17 +136,18 @@ impl Emulator {
 
     /// Sets up program run by the program. This method analyzes the passed content
     /// and configure CPU properly. If the passed contend doesn't seem ELF file,
-    /// it panics. This method is expected to be called only once.
+    /// it returns `Err`. This method is expected to be called only once.
     ///
     /// # Arguments
     /// * `data` Program binary
     // @TODO: Make ElfAnalyzer and move the core logic there.
-    // @TODO: Returns `Err` if the passed contend doesn't seem ELF file
-    pub fn setup_program(&mut self, data: Vec<u8>) {
+    pub fn setup_program(&mut self, data: Vec<u8>) -> Result<(), TracerError> {
         let analyzer = ElfAnalyzer::new(data);
 
         if !analyzer.validate() {
-            panic!("This file does not seem ELF file");
+            return Err(TracerError::MalformedElf(
+                "missing ELF magic number".to_string(),
+            ));
         }
 
         let header = analyzer.read_header();
@@ -204,6 +206,16 @@ impl Emulator {
             let sh_offset = header.sh_offset as usize;
             let sh_size = header.sh_size as usize;
             if sh_addr >= 0x80000000 && sh_offset > 0 && sh_size > 0 {
+                let end_address = sh_addr + sh_size as u64 - 1;
+                if !matches!(
+                    self.cpu.get_mut_mmu().validate_address(end_address),
+                    Ok(true)
+                ) {
+                    return Err(TracerError::MalformedElf(format!(
+                        "section at {:#x} does not fit in memory",
+                        sh_addr
+                    )));
+                }
                 for j in 0..sh_size {
                     self.cpu
                         .get_mut_mmu()
@@ -213,6 +225,7 @@ impl Emulator {
         }
 
         self.cpu.update_pc(header.e_entry);
+        Ok(())
     }
 
     /// Loads symbols of program and adds them to `symbol_map`.
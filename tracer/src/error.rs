@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TracerError {
+    #[error("failed to read ELF: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("malformed ELF: {0}")]
+    MalformedElf(String),
+    #[error("unsupported instruction {word:#010x} at pc {pc:#x}")]
+    UnsupportedInstruction { pc: u64, word: u32 },
+    #[error("out-of-bounds memory access to {address:#x} at pc {pc:#x}")]
+    MemoryOutOfBounds { pc: u64, address: u64 },
+    #[error("guest did not terminate within {max_cycles} cycles (pc = {pc:#x})")]
+    CycleBudgetExceeded { max_cycles: u64, pc: u64 },
+}
+
+impl From<object::read::Error> for TracerError {
+    fn from(err: object::read::Error) -> Self {
+        TracerError::MalformedElf(err.to_string())
+    }
+}
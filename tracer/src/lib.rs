@@ -7,19 +7,29 @@ use common::{self, constants::RAM_START_ADDRESS};
 use emulator::{
     cpu::{self, Xlen},
     default_terminal::DefaultTerminal,
+    elf_analyzer::ElfAnalyzer,
     Emulator,
 };
 
 use object::{Object, ObjectSection, SectionKind};
 
+mod debugger;
 mod decode;
 mod emulator;
+mod error;
 mod trace;
 
 pub use common::rv_trace::{
     ELFInstruction, JoltDevice, MemoryState, RVTraceRow, RegisterState, RV32IM,
 };
 
+pub use common::precompiles::{
+    keccak_f, sha256_compress, KECCAK_ROTATIONS, KECCAK_ROUND_CONSTANTS, SHA256_K,
+};
+pub use debugger::{Debugger, StopReason};
+pub use emulator::cpu::CustomInstruction;
+pub use error::TracerError;
+
 use crate::decode::decode_raw;
 
 #[tracing::instrument(skip_all)]
@@ -28,50 +38,116 @@ pub fn trace(
     inputs: &[u8],
     input_size: u64,
     output_size: u64,
-) -> (Vec<RVTraceRow>, JoltDevice) {
-    let term = DefaultTerminal::new();
-    let mut emulator = Emulator::new(Box::new(term));
-    emulator.update_xlen(get_xlen());
+    max_cycles: u64,
+) -> Result<(Vec<RVTraceRow>, JoltDevice), TracerError> {
+    let mut elf_file = File::open(elf)?;
 
-    let mut jolt_device = JoltDevice::new(input_size, output_size);
-    jolt_device.inputs = inputs.to_vec();
-    emulator.get_mut_cpu().get_mut_mmu().jolt_device = jolt_device;
+    let mut elf_contents = Vec::new();
+    elf_file.read_to_end(&mut elf_contents)?;
 
-    let mut elf_file = File::open(elf).unwrap();
+    trace_from_bytes(&elf_contents, inputs, input_size, output_size, max_cycles)
+}
 
-    let mut elf_contents = Vec::new();
-    elf_file.read_to_end(&mut elf_contents).unwrap();
+/// Like [`trace`], but runs an ELF that is already in memory.
+#[tracing::instrument(skip_all)]
+pub fn trace_from_bytes(
+    elf: &[u8],
+    inputs: &[u8],
+    input_size: u64,
+    output_size: u64,
+    max_cycles: u64,
+) -> Result<(Vec<RVTraceRow>, JoltDevice), TracerError> {
+    let mut rows = Vec::new();
+    let (device, _) = trace_with_sink(
+        elf,
+        inputs,
+        input_size,
+        output_size,
+        max_cycles,
+        &[],
+        |row| rows.push(row),
+    )?;
+    Ok((rows, device))
+}
 
-    emulator.setup_program(elf_contents);
+/// Runs an in-memory ELF, passing each trace row to `sink` as soon as its instruction
+/// has executed. Unlike [`trace_from_bytes`], the trace is never held in memory as a
+/// whole, so the caller decides what to keep of it.
+///
+/// The guest may execute `custom_instructions`, see [`CustomInstruction`]. Besides the
+/// program IO, returns the bytes the guest printed to its log.
+#[tracing::instrument(skip_all)]
+pub fn trace_with_sink(
+    elf: &[u8],
+    inputs: &[u8],
+    input_size: u64,
+    output_size: u64,
+    max_cycles: u64,
+    custom_instructions: &[CustomInstruction],
+    mut sink: impl FnMut(RVTraceRow),
+) -> Result<(JoltDevice, Vec<u8>), TracerError> {
+    let mut emulator = setup_emulator(elf, inputs, input_size, output_size)?;
+    emulator
+        .get_mut_cpu()
+        .set_custom_instructions(custom_instructions);
 
-    let mut prev_pc = 0;
-    loop {
-        let pc = emulator.get_cpu().read_pc();
+    // The guest signals that it is done by writing to the termination address, either
+    // after `main` returns or from its panic handler.
+    let mut cycles = 0;
+    while !emulator.get_cpu().get_mmu().jolt_device.termination {
+        if cycles == max_cycles {
+            return Err(TracerError::CycleBudgetExceeded {
+                max_cycles,
+                pc: emulator.get_cpu().read_pc(),
+            });
+        }
         emulator.tick();
-
-        // This is a trick to see if the program has terminated by throwing itself
-        // into an infinite loop. It seems to be a good heuristic for now but we
-        // should eventually migrate to an explicit shutdown signal.
-        if prev_pc == pc {
-            break;
+        if let Some(err) = emulator.get_mut_cpu().take_fault() {
+            return Err(err);
         }
-
-        prev_pc = pc;
+        emulator
+            .get_cpu()
+            .tracer
+            .rows
+            .borrow_mut()
+            .drain(..)
+            .for_each(&mut sink);
+        cycles += 1;
     }
 
-    let mut rows = emulator.get_mut_cpu().tracer.rows.try_borrow_mut().unwrap();
-    let mut output = Vec::new();
-    output.append(&mut rows);
-    drop(rows);
+    let mmu = emulator.get_mut_cpu().get_mut_mmu();
+    let device = mmu.jolt_device.clone();
+    let log = std::mem::take(&mut mmu.log);
+
+    Ok((device, log))
+}
 
-    let device = emulator.get_mut_cpu().get_mut_mmu().jolt_device.clone();
+/// Creates an emulator with `elf` loaded and `inputs` available to the guest.
+fn setup_emulator(
+    elf: &[u8],
+    inputs: &[u8],
+    input_size: u64,
+    output_size: u64,
+) -> Result<Emulator, TracerError> {
+    let term = DefaultTerminal::new();
+    let mut emulator = Emulator::new(Box::new(term));
+    emulator.update_xlen(get_xlen());
+
+    let mut jolt_device = JoltDevice::new(input_size, output_size);
+    jolt_device.inputs = inputs.to_vec();
+    emulator.get_mut_cpu().get_mut_mmu().jolt_device = jolt_device;
 
-    (output, device)
+    // The emulator's ELF loader assumes a well-formed file, so reject anything `object`
+    // cannot read before handing it over.
+    parse_elf(elf)?;
+    emulator.setup_program(elf.to_vec())?;
+    Ok(emulator)
 }
 
+#[allow(clippy::type_complexity)]
 #[tracing::instrument(skip_all)]
-pub fn decode(elf: &[u8]) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
-    let obj = object::File::parse(elf).unwrap();
+pub fn decode(elf: &[u8]) -> Result<(Vec<ELFInstruction>, Vec<(u64, u8)>), TracerError> {
+    let obj = parse_elf(elf)?;
 
     let sections = obj
         .sections()
@@ -82,16 +158,37 @@ pub fn decode(elf: &[u8]) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
     let mut data = Vec::new();
 
     for section in sections {
-        let raw_data = section.data().unwrap();
+        let raw_data = section.data()?;
 
         if let SectionKind::Text = section.kind() {
-            for (chunk, word) in raw_data.chunks(4).enumerate() {
-                let word = u32::from_le_bytes(word.try_into().unwrap());
-                let address = chunk as u64 * 4 + section.address();
+            let malformed = || {
+                TracerError::MalformedElf(format!(
+                    "text section at {:#x} is not a whole number of instructions",
+                    section.address()
+                ))
+            };
+            let mut offset = 0;
+            while offset < raw_data.len() {
+                let address = section.address() + offset as u64;
+                let halfword = raw_data.get(offset..offset + 2).ok_or_else(malformed)?;
+                let halfword = u16::from_le_bytes([halfword[0], halfword[1]]) as u32;
+
+                // Compressed instructions are 16 bits wide and expanded to their 32-bit
+                // equivalents, but keep their own address.
+                let is_compressed = halfword & 0x3 != 0x3;
+                let word = if is_compressed {
+                    offset += 2;
+                    cpu::Cpu::uncompress(halfword, &get_xlen())
+                } else {
+                    let word = raw_data.get(offset..offset + 4).ok_or_else(malformed)?;
+                    offset += 4;
+                    u32::from_le_bytes([word[0], word[1], word[2], word[3]])
+                };
 
                 if let Ok(inst) = decode_raw(word) {
                     if let Some(trace) = inst.trace {
-                        let inst = trace(&inst, &get_xlen(), word, address);
+                        let mut inst = trace(&inst, &get_xlen(), word, address);
+                        inst.is_compressed = is_compressed;
                         instructions.push(inst);
                         continue;
                     }
@@ -105,6 +202,7 @@ pub fn decode(elf: &[u8]) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
                     rd: None,
                     imm: None,
                     virtual_sequence_remaining: None,
+                    is_compressed,
                 });
             }
         }
@@ -114,7 +212,44 @@ pub fn decode(elf: &[u8]) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
         }
     }
 
-    (instructions, data)
+    Ok((instructions, data))
+}
+
+/// A function symbol of a guest ELF, with its (mangled) name.
+#[derive(Clone, Debug)]
+pub struct FunctionSymbol {
+    pub name: String,
+    pub address: u64,
+    pub size: u64,
+}
+
+/// Reads the function symbols from the symbol tables of `elf`, sorted by address.
+pub fn function_symbols(elf: &[u8]) -> Result<Vec<FunctionSymbol>, TracerError> {
+    parse_elf(elf)?;
+    let analyzer = ElfAnalyzer::new(elf.to_vec());
+    let header = analyzer.read_header();
+    let section_headers = analyzer.read_section_headers(&header);
+
+    let mut symbols: Vec<_> = analyzer
+        .read_function_symbols(&header, &section_headers)
+        .into_iter()
+        .map(|(name, address, size)| FunctionSymbol {
+            name,
+            address,
+            size,
+        })
+        .collect();
+    symbols.sort_by_key(|symbol| symbol.address);
+    Ok(symbols)
+}
+
+/// Parses `elf`, checking that the contents of all of its sections are within the file.
+fn parse_elf(elf: &[u8]) -> Result<object::File<'_>, TracerError> {
+    let obj = object::File::parse(elf)?;
+    for section in obj.sections() {
+        section.data()?;
+    }
+    Ok(obj)
 }
 
 fn get_xlen() -> Xlen {
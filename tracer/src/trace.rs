@@ -21,11 +21,13 @@ impl Tracer {
         let mut inst = inst;
         inst.address = inst.address as u32 as u64;
         *self.open.try_borrow_mut().unwrap() = true;
+        let precompile_input = inst.opcode.is_precompile().then(Vec::new);
         self.rows.try_borrow_mut().unwrap().push(RVTraceRow {
             instruction: inst,
             register_state: RegisterState::default(),
             memory_state: None,
             advice_value: None,
+            precompile_input,
         });
     }
 
@@ -65,10 +67,37 @@ impl Tracer {
         }
 
         if let Some(row) = self.rows.try_borrow_mut().unwrap().last_mut() {
+            // A precompile accesses many words. Only the ones it reads are recorded, since
+            // the host recomputes the words it writes when expanding it.
+            if let Some(input) = row.precompile_input.as_mut() {
+                if let MemoryState::Read { value, .. } = memory_state {
+                    input.push(value);
+                }
+                return;
+            }
+            // An AMO reads a word and then writes it back; keep the value it read as advice,
+            // since rd may be x0 and the write replaces the memory state.
+            if let (Some(MemoryState::Read { value, .. }), MemoryState::Write { .. }) =
+                (&row.memory_state, &memory_state)
+            {
+                row.advice_value = Some(*value);
+            }
             row.memory_state = Some(memory_state);
         }
     }
 
+    /// Records a value the instruction's virtual sequence needs, but which is neither an
+    /// operand nor a memory access of the instruction.
+    pub fn push_advice(&self, value: u64) {
+        if !*self.open.try_borrow().unwrap() {
+            return;
+        }
+
+        if let Some(row) = self.rows.try_borrow_mut().unwrap().last_mut() {
+            row.advice_value = Some(value);
+        }
+    }
+
     pub fn end_instruction(&self) {
         *self.open.try_borrow_mut().unwrap() = false;
     }